@@ -0,0 +1,103 @@
+import ElowenProgram from '../program'
+import { PublicKey, Transaction } from '@solana/web3.js'
+import { AdminRoles, SolanaAddress } from '../types'
+import { getMultisigVaultPda, maybeToPublicKey, toAdminRolesFormat } from '../utils'
+
+export function findAdminConfigAccount() {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('admin_config')],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+// signed by the multisig vault, which becomes the first owner
+export async function createInitializeAdminConfigInstruction(
+    roles: AdminRoles,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .initializeAdminConfig(toAdminRolesFormat(roles))
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .instruction()
+}
+
+export async function createInitializeAdminConfigTransaction(
+    roles: AdminRoles,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(await createInitializeAdminConfigInstruction(roles, signer))
+}
+
+export async function createUpdateAdminRolesInstruction(
+    roles: AdminRoles,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .updateAdminRoles(toAdminRolesFormat(roles))
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .instruction()
+}
+
+export async function createUpdateAdminRolesTransaction(
+    roles: AdminRoles,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(await createUpdateAdminRolesInstruction(roles, signer))
+}
+
+// the new owner takes over only after accepting
+export async function createTransferAdminOwnershipInstruction(
+    newOwner: SolanaAddress,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .transferAdminOwnership(maybeToPublicKey(newOwner))
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .instruction()
+}
+
+export async function createTransferAdminOwnershipTransaction(
+    newOwner: SolanaAddress,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(await createTransferAdminOwnershipInstruction(newOwner, signer))
+}
+
+export async function createAcceptAdminOwnershipInstruction(pendingOwner: SolanaAddress) {
+    return await ElowenProgram.methods
+        .acceptAdminOwnership()
+        .accounts({
+            signer: maybeToPublicKey(pendingOwner)
+        })
+        .instruction()
+}
+
+export async function createAcceptAdminOwnershipTransaction(pendingOwner: SolanaAddress) {
+    return new Transaction().add(await createAcceptAdminOwnershipInstruction(pendingOwner))
+}
+
+export async function getAdminConfigAccountData() {
+    const result = await ElowenProgram.accounts.adminConfigAccount.fetchNullable(
+        findAdminConfigAccount()
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        owner: result.owner,
+        pendingOwner: result.pendingOwner,
+        roles: {
+            operator: result.roles.operator,
+            treasurer: result.roles.treasurer,
+            rewardDistributor: result.roles.rewardDistributor,
+            liquidityManager: result.roles.liquidityManager
+        }
+    }
+}
@@ -1,9 +1,11 @@
 import ElowenProgram from '../program'
 import { getElwMint } from './platform'
+import { findSpendingLimitAccount } from './withdrawal'
 import { Transaction } from '@solana/web3.js'
 import { Currency, SolanaAddress, VaultAccount } from '../types'
 import { getAssociatedTokenAddressSync, TOKEN_PROGRAM_ID } from '@solana/spl-token'
 import {
+    WSOL_MINT,
     formatNumber,
     getMultisigVaultPda,
     getQuoteMint,
@@ -27,6 +29,7 @@ export async function createWithdrawEdaElwInstruction(receiver: SolanaAddress, a
             receiver: maybeToPublicKey(receiver)
         })
         .accountsPartial({
+            spendingLimit: findSpendingLimitAccount(VaultAccount.Eda, elwMint),
             receiverTokenAta: getAssociatedTokenAddressSync(elwMint, maybeToPublicKey(receiver))
         })
         .instruction()
@@ -43,6 +46,9 @@ export async function createWithdrawEdaUsdcInstruction(receiver: SolanaAddress,
             signer: getMultisigVaultPda(),
             receiver: maybeToPublicKey(receiver)
         })
+        .accountsPartial({
+            spendingLimit: findSpendingLimitAccount(VaultAccount.Eda, getQuoteMint(Currency.USDC))
+        })
         .instruction()
 }
 
@@ -50,6 +56,43 @@ export async function createWithdrawEdaUsdcTransaction(receiver: SolanaAddress,
     return new Transaction().add(await createWithdrawEdaUsdcInstruction(receiver, amount))
 }
 
+// registered payment mints, the token program follows the owner of the mint
+export async function createWithdrawEdaTokenInstruction(
+    receiver: SolanaAddress,
+    paymentMint: SolanaAddress,
+    amount: number,
+    decimals: number
+) {
+    const mint = maybeToPublicKey(paymentMint)
+    const mintInfo = await ElowenProgram.connection.getAccountInfo(mint)
+    if (!mintInfo) {
+        throw new Error(`Payment mint not found: ${mint.toBase58()}`)
+    }
+    return await ElowenProgram.methods
+        .withdrawEdaToken(toTokenFormat(amount, decimals))
+        .accounts({
+            paymentMint: mint,
+            tokenProgram: mintInfo.owner,
+            signer: getMultisigVaultPda(),
+            receiver: maybeToPublicKey(receiver)
+        })
+        .accountsPartial({
+            spendingLimit: findSpendingLimitAccount(VaultAccount.Eda, mint)
+        })
+        .instruction()
+}
+
+export async function createWithdrawEdaTokenTransaction(
+    receiver: SolanaAddress,
+    paymentMint: SolanaAddress,
+    amount: number,
+    decimals: number
+) {
+    return new Transaction().add(
+        await createWithdrawEdaTokenInstruction(receiver, paymentMint, amount, decimals)
+    )
+}
+
 export async function createWithdrawEdaSolInstruction(receiver: SolanaAddress, amount: number) {
     return ElowenProgram.methods
         .withdrawEdaSol(toTokenFormat(amount))
@@ -57,6 +100,9 @@ export async function createWithdrawEdaSolInstruction(receiver: SolanaAddress, a
             signer: getMultisigVaultPda(),
             receiver: maybeToPublicKey(receiver)
         })
+        .accountsPartial({
+            spendingLimit: findSpendingLimitAccount(VaultAccount.Eda, WSOL_MINT)
+        })
         .instruction()
 }
 
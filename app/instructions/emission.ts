@@ -0,0 +1,87 @@
+import ElowenProgram from '../program'
+import { PublicKey, Transaction } from '@solana/web3.js'
+import { EmissionSchedule, SolanaAddress } from '../types'
+import {
+    emissionCurveFromRustEnum,
+    fromTokenFormat,
+    getMultisigVaultPda,
+    maybeToPublicKey,
+    toBn,
+    toEmissionScheduleFormat
+} from '../utils'
+
+export function findEmissionScheduleAccount() {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('emission_schedule')],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+// signed by the owner, the multisig vault by default
+export async function createInitializeEmissionScheduleInstruction(
+    schedule: EmissionSchedule,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .initializeEmissionSchedule(toEmissionScheduleFormat(schedule))
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .instruction()
+}
+
+export async function createInitializeEmissionScheduleTransaction(
+    schedule: EmissionSchedule,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(
+        await createInitializeEmissionScheduleInstruction(schedule, signer)
+    )
+}
+
+export async function createUpdateEmissionScheduleInstruction(
+    schedule: EmissionSchedule,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .updateEmissionSchedule(toEmissionScheduleFormat(schedule))
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .instruction()
+}
+
+export async function createUpdateEmissionScheduleTransaction(
+    schedule: EmissionSchedule,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(await createUpdateEmissionScheduleInstruction(schedule, signer))
+}
+
+// monthly emission at the given unix time, read by simulating the view instruction
+export async function getEmission(timestamp: number = Math.floor(Date.now() / 1000)) {
+    const result = await ElowenProgram.methods.getEmission(toBn(timestamp)).view()
+    return fromTokenFormat(result)
+}
+
+export async function getEmissionScheduleAccountData() {
+    const result = await ElowenProgram.accounts.emissionScheduleAccount.fetchNullable(
+        findEmissionScheduleAccount()
+    )
+    if (!result) {
+        return null
+    }
+    const { schedule } = result
+    return {
+        curve: emissionCurveFromRustEnum(schedule.curve),
+        baseAmount: fromTokenFormat(schedule.baseAmount),
+        halvingMonths: schedule.halvingMonths,
+        decayAmount: fromTokenFormat(schedule.decayAmount),
+        minAmount: fromTokenFormat(schedule.minAmount),
+        steps: schedule.steps.map((step) => ({
+            fromMonth: step.fromMonth,
+            amount: fromTokenFormat(step.amount)
+        }))
+    }
+}
@@ -6,7 +6,9 @@ export async function listenClaimRewardEvent(callback: (event: ClaimRewardEvent)
     const subscriptionId = ElowenProgram.addEventListener('claimRewardEvent', (event) => {
         callback({
             receiver: event.receiver,
-            amount: fromTokenFormat(event.amount)
+            amount: fromTokenFormat(event.amount),
+            pool: event.pool,
+            epoch: event.epoch
         })
     })
 
@@ -36,7 +38,8 @@ export async function listenBuyPresaleTokenEvent(callback: (event: BuyPresaleTok
     const subscriptionId = ElowenProgram.addEventListener('buyPresaleTokenEvent', (event) => {
         callback({
             receiver: event.receiver,
-            amount: fromTokenFormat(event.amount)
+            amount: fromTokenFormat(event.amount),
+            round: event.round
         })
     })
 
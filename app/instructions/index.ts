@@ -1,9 +1,12 @@
 export * from './elw'
+export * from './admin'
 export * from './alt'
 export * from './platform'
 export * from './events'
 export * from './reward'
+export * from './emission'
 export * from './eda'
+export * from './withdrawal'
 export * from './team'
 export * from './premium'
 export * from './liquidity'
@@ -4,7 +4,15 @@ import { Wallet } from '@coral-xyz/anchor'
 import { PublicKey, Transaction } from '@solana/web3.js'
 import { getAssociatedTokenAddressSync } from '@solana/spl-token'
 import { PythSolanaReceiver } from '@pythnetwork/pyth-solana-receiver'
-import { PresaleType, PresaleTypeMap, QuoteCurrency, SolanaAddress, VaultAccount } from '../types'
+import {
+    AllowlistProof,
+    Currency,
+    PresaleType,
+    PresaleTypeMap,
+    QuoteCurrency,
+    SolanaAddress,
+    VaultAccount
+} from '../types'
 import {
     maybeToPublicKey,
     currencyToRustEnum,
@@ -17,6 +25,63 @@ import {
     getTokenAccountInfo
 } from '../utils'
 
+function findReferralAccount(referrer: SolanaAddress) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('referral'), maybeToPublicKey(referrer).toBuffer()],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findAllowlistAccount(receiver: SolanaAddress) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('allowlist'), maybeToPublicKey(receiver).toBuffer()],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findPaymentMintAccount(paymentMint: SolanaAddress) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('payment_mint'), maybeToPublicKey(paymentMint).toBuffer()],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findPresalePaymentAccount(
+    receiver: SolanaAddress,
+    paymentMint: SolanaAddress,
+    presaleType: PresaleType
+) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('presale_payment'),
+            maybeToPublicKey(receiver).toBuffer(),
+            maybeToPublicKey(paymentMint).toBuffer(),
+            Buffer.from([PresaleTypeMap[presaleType]])
+        ],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findPresaleReceiptMint(presaleType: PresaleType) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('presale_receipt'), Buffer.from([PresaleTypeMap[presaleType]])],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findPresaleReceiptSummaryAccount(presaleType: PresaleType) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('presale_receipt_summary'), Buffer.from([PresaleTypeMap[presaleType]])],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
 function findPresalePurchaseAccount(receiver: SolanaAddress, presaleType: PresaleType) {
     const [pda, _bump] = PublicKey.findProgramAddressSync(
         [
@@ -33,7 +98,10 @@ export async function createBuyPresaleElwInstruction(
     receiver: SolanaAddress,
     amountToBuy: number,
     currency: QuoteCurrency,
-    presaleType: PresaleType
+    presaleType: PresaleType,
+    maxPaymentAmount: number,
+    allowlistProof: AllowlistProof | null = null,
+    referrer: SolanaAddress | null = null
 ) {
     const [elwMint, presaleTokenAta] = await Promise.all([
         getElwMint(),
@@ -47,13 +115,22 @@ export async function createBuyPresaleElwInstruction(
         .buyPresaleElw(
             presaleTypeToRustEnum(presaleType),
             toTokenFormat(amountToBuy),
-            currencyToRustEnum(currency)
+            currencyToRustEnum(currency),
+            toTokenFormat(maxPaymentAmount, currency === Currency.USDC ? 6 : 9),
+            allowlistProof && {
+                allocation: toTokenFormat(allowlistProof.allocation),
+                proof: allowlistProof.proof
+            },
+            referrer && maybeToPublicKey(referrer)
         )
         .accounts({
             elwMint,
             presaleTokenAta,
             receiver: maybeToPublicKey(receiver),
             receiverPurchaseAccount: findPresalePurchaseAccount(receiver, presaleType),
+            // only needed with a proof, before the public phase
+            receiverAllowlistAccount: allowlistProof && findAllowlistAccount(receiver),
+            referralAccount: referrer && findReferralAccount(referrer),
             priceUpdate: pythProgram.getPriceFeedAccountAddress(
                 0,
                 'ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d'
@@ -66,10 +143,21 @@ export async function createBuyPresaleElwTransaction(
     receiver: SolanaAddress,
     amountToBuy: number,
     currency: QuoteCurrency,
-    presaleType: PresaleType
+    presaleType: PresaleType,
+    maxPaymentAmount: number,
+    allowlistProof: AllowlistProof | null = null,
+    referrer: SolanaAddress | null = null
 ) {
     return new Transaction().add(
-        await createBuyPresaleElwInstruction(receiver, amountToBuy, currency, presaleType)
+        await createBuyPresaleElwInstruction(
+            receiver,
+            amountToBuy,
+            currency,
+            presaleType,
+            maxPaymentAmount,
+            allowlistProof,
+            referrer
+        )
     )
 }
 
@@ -102,6 +190,106 @@ export async function createClaimPresaleElwTransaction(
     return new Transaction().add(await createClaimPresaleElwInstruction(receiver, presaleType))
 }
 
+// mints receipts 1:1 for the unclaimed part of a final position
+export async function createTokenizePresalePositionInstruction(
+    receiver: SolanaAddress,
+    presaleType: PresaleType,
+    amount: number
+) {
+    const receiptMint = findPresaleReceiptMint(presaleType)
+    return await ElowenProgram.methods
+        .tokenizePresalePosition(presaleTypeToRustEnum(presaleType), toTokenFormat(amount))
+        .accounts({
+            receiver: maybeToPublicKey(receiver)
+        })
+        .accountsPartial({
+            receiverPurchaseAccount: findPresalePurchaseAccount(receiver, presaleType),
+            receiptMint,
+            receiptSummaryAccount: findPresaleReceiptSummaryAccount(presaleType),
+            receiverReceiptAta: getAssociatedTokenAddressSync(
+                receiptMint,
+                maybeToPublicKey(receiver)
+            )
+        })
+        .instruction()
+}
+
+export async function createTokenizePresalePositionTransaction(
+    receiver: SolanaAddress,
+    presaleType: PresaleType,
+    amount: number
+) {
+    return new Transaction().add(
+        await createTokenizePresalePositionInstruction(receiver, presaleType, amount)
+    )
+}
+
+// a final position moves whole to the new owner, the owner account is closed
+export async function createTransferPresalePositionInstruction(
+    owner: SolanaAddress,
+    newOwner: SolanaAddress,
+    presaleType: PresaleType
+) {
+    return await ElowenProgram.methods
+        .transferPresalePosition(presaleTypeToRustEnum(presaleType))
+        .accounts({
+            owner: maybeToPublicKey(owner),
+            newOwner: maybeToPublicKey(newOwner)
+        })
+        .accountsPartial({
+            ownerPurchaseAccount: findPresalePurchaseAccount(owner, presaleType),
+            newOwnerPurchaseAccount: findPresalePurchaseAccount(newOwner, presaleType)
+        })
+        .instruction()
+}
+
+export async function createTransferPresalePositionTransaction(
+    owner: SolanaAddress,
+    newOwner: SolanaAddress,
+    presaleType: PresaleType
+) {
+    return new Transaction().add(
+        await createTransferPresalePositionInstruction(owner, newOwner, presaleType)
+    )
+}
+
+// a referrer registers once and shares the address with the buyers
+export async function createRegisterReferrerInstruction(referrer: SolanaAddress) {
+    return await ElowenProgram.methods
+        .registerReferrer()
+        .accounts({
+            referrer: maybeToPublicKey(referrer)
+        })
+        .instruction()
+}
+
+export async function createRegisterReferrerTransaction(referrer: SolanaAddress) {
+    return new Transaction().add(await createRegisterReferrerInstruction(referrer))
+}
+
+export async function createClaimReferralRewardInstruction(referrer: SolanaAddress) {
+    const [elwMint, presaleTokenAta] = await Promise.all([
+        getElwMint(),
+        getVaultAccountElwAta(VaultAccount.Presale)
+    ])
+    return await ElowenProgram.methods
+        .claimReferralReward()
+        .accounts({
+            elwMint,
+            referrer: maybeToPublicKey(referrer)
+        })
+        .accountsPartial({
+            presaleTokenAta,
+            referralAccount: findReferralAccount(referrer),
+            referrerTokenAta: getAssociatedTokenAddressSync(elwMint, maybeToPublicKey(referrer))
+        })
+        .instruction()
+}
+
+export async function createClaimReferralRewardTransaction(referrer: SolanaAddress) {
+    return new Transaction().add(await createClaimReferralRewardInstruction(referrer))
+}
+
 export async function createBurnUnsoldElwInstruction() {
     const [elwMint, presaleTokenAta] = await Promise.all([
         getElwMint(),
@@ -123,6 +311,119 @@ export async function createBurnUnsoldElwTransaction() {
     return new Transaction().add(await createBurnUnsoldElwInstruction())
 }
 
+async function getPaymentMintTokenProgram(paymentMint: PublicKey) {
+    const mintInfo = await ElowenProgram.connection.getAccountInfo(paymentMint)
+    if (!mintInfo) {
+        throw new Error(`Payment mint not found: ${paymentMint.toBase58()}`)
+    }
+    return mintInfo.owner
+}
+
+// a missed soft cap refunds the SOL and USDC paid and cancels the position
+export async function createRefundPresaleInstruction(
+    receiver: SolanaAddress,
+    presaleType: PresaleType
+) {
+    return await ElowenProgram.methods
+        .refundPresale(presaleTypeToRustEnum(presaleType))
+        .accounts({
+            receiver: maybeToPublicKey(receiver)
+        })
+        .accountsPartial({
+            receiverPurchaseAccount: findPresalePurchaseAccount(receiver, presaleType)
+        })
+        .instruction()
+}
+
+export async function createRefundPresaleTransaction(
+    receiver: SolanaAddress,
+    presaleType: PresaleType
+) {
+    return new Transaction().add(await createRefundPresaleInstruction(receiver, presaleType))
+}
+
+export async function createRefundPresaleTokenInstruction(
+    receiver: SolanaAddress,
+    paymentMint: SolanaAddress,
+    presaleType: PresaleType
+) {
+    const mint = maybeToPublicKey(paymentMint)
+    return await ElowenProgram.methods
+        .refundPresaleToken(presaleTypeToRustEnum(presaleType))
+        .accounts({
+            paymentMint: mint,
+            tokenProgram: await getPaymentMintTokenProgram(mint),
+            receiver: maybeToPublicKey(receiver)
+        })
+        .accountsPartial({
+            receiverPaymentAccount: findPresalePaymentAccount(receiver, mint, presaleType),
+            receiverPurchaseAccount: findPresalePurchaseAccount(receiver, presaleType)
+        })
+        .instruction()
+}
+
+export async function createRefundPresaleTokenTransaction(
+    receiver: SolanaAddress,
+    paymentMint: SolanaAddress,
+    presaleType: PresaleType
+) {
+    return new Transaction().add(
+        await createRefundPresaleTokenInstruction(receiver, paymentMint, presaleType)
+    )
+}
+
+// permissionless once the soft cap is reached, the payer covers the missing vault accounts
+export async function createReleasePresaleFundsInstruction(payer: SolanaAddress) {
+    return await ElowenProgram.methods
+        .releasePresaleFunds()
+        .accounts({
+            payer: maybeToPublicKey(payer)
+        })
+        .instruction()
+}
+
+export async function createReleasePresaleFundsTransaction(payer: SolanaAddress) {
+    return new Transaction().add(await createReleasePresaleFundsInstruction(payer))
+}
+
+export async function createReleasePresaleTokenFundsInstruction(
+    payer: SolanaAddress,
+    paymentMint: SolanaAddress
+) {
+    const mint = maybeToPublicKey(paymentMint)
+    return await ElowenProgram.methods
+        .releasePresaleTokenFunds()
+        .accounts({
+            paymentMint: mint,
+            tokenProgram: await getPaymentMintTokenProgram(mint),
+            payer: maybeToPublicKey(payer)
+        })
+        .instruction()
+}
+
+export async function createReleasePresaleTokenFundsTransaction(
+    payer: SolanaAddress,
+    paymentMint: SolanaAddress
+) {
+    return new Transaction().add(
+        await createReleasePresaleTokenFundsInstruction(payer, paymentMint)
+    )
+}
+
+// moves a summary account created before rounds, refunds and referrals to the current layout
+export async function createMigratePresaleSummaryInstruction(payer: SolanaAddress) {
+    return await ElowenProgram.methods
+        .migratePresaleSummaryAccount()
+        .accounts({
+            payer: maybeToPublicKey(payer)
+        })
+        .instruction()
+}
+
+export async function createMigratePresaleSummaryTransaction(payer: SolanaAddress) {
+    return new Transaction().add(await createMigratePresaleSummaryInstruction(payer))
+}
+
 export async function getPresalePurchaseAccountData(
     receiver: SolanaAddress,
     presaleType: PresaleType
@@ -138,6 +439,9 @@ export async function getPresalePurchaseAccountData(
         presaleType: result.presaleType,
         receiver: maybeToPublicKey(receiver),
         amount: fromTokenFormat(result.amount),
+        claimedAmount: fromTokenFormat(result.claimedAmount),
+        solPaid: fromTokenFormat(result.solPaid),
+        usdcPaid: fromTokenFormat(result.usdcPaid, 6),
         unlockTime: result.unlockTime.toNumber(),
         amountFormatted: formatNumber(fromTokenFormat(result.amount)),
         presaleTypeFormatted: findPresaleTypeFromNumber(result.presaleType),
@@ -145,6 +449,38 @@ export async function getPresalePurchaseAccountData(
     }
 }
 
+export async function getPresaleAllowlistAccountData(receiver: SolanaAddress) {
+    const result = await ElowenProgram.accounts.allowlistAccount.fetchNullable(
+        findAllowlistAccount(receiver)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        purchasedAmount: fromTokenFormat(result.purchasedAmount)
+    }
+}
+
+export async function getReferralAccountData(referrer: SolanaAddress) {
+    const result = await ElowenProgram.accounts.referralAccount.fetchNullable(
+        findReferralAccount(referrer)
+    )
+    if (!result) {
+        return null
+    }
+    const earnedAmount = fromTokenFormat(result.earnedAmount)
+    const claimedAmount = fromTokenFormat(result.claimedAmount)
+    return {
+        referrer: result.referrer,
+        purchaseCount: result.purchaseCount,
+        tokenVolume: fromTokenFormat(result.tokenVolume),
+        usdcVolume: fromTokenFormat(result.usdcVolume, 6),
+        earnedAmount,
+        claimedAmount,
+        claimableAmount: earnedAmount - claimedAmount
+    }
+}
+
 export async function getPresaleSummaryAccountData() {
     const result = await ElowenProgram.accounts.summaryAccount.fetchNullable(
         PublicKey.findProgramAddressSync([Buffer.from('presale_summary')], ElowenProgram.ID)[0]
@@ -162,6 +498,9 @@ export async function getPresaleSummaryAccountData() {
     const usdcSentToEda = fromTokenFormat(result.usdcSentToEda, 6)
     const solSentToLiquidity = fromTokenFormat(result.solSentToLiquidity)
     const usdcSentToLiquidity = fromTokenFormat(result.usdcSentToLiquidity, 6)
+    const solRefunded = fromTokenFormat(result.solRefunded)
+    const usdcRefunded = fromTokenFormat(result.usdcRefunded, 6)
+    const referralBonusAmount = fromTokenFormat(result.referralBonusAmount)
     return {
         solRaised,
         usdcRaised,
@@ -173,7 +512,11 @@ export async function getPresaleSummaryAccountData() {
         tokenSoldForUsdc,
         solSentToLiquidity,
         usdcSentToLiquidity,
+        solRefunded,
+        usdcRefunded,
+        referralBonusAmount,
         remainingAmount: totalAmount - tokenSold,
+        isFundsReleased: result.isFundsReleased,
         isUnsoldTokensBurned: result.isUnsoldTokensBurned,
         solRaisedFormatted: formatNumber(solRaised),
         usdRaisedFormatted: formatNumber(usdcRaised),
@@ -189,6 +532,115 @@ export async function getPresaleSummaryAccountData() {
     }
 }
 
+export async function getPresaleConfigAccountData() {
+    const result = await ElowenProgram.accounts.presaleConfigAccount.fetchNullable(
+        PublicKey.findProgramAddressSync([Buffer.from('presale_config')], ElowenProgram.ID)[0]
+    )
+    if (!result) {
+        return null
+    }
+    const { rules } = result
+    return {
+        isFrozen: result.isFrozen,
+        totalAmount: fromTokenFormat(rules.totalAmount),
+        softCap: fromTokenFormat(rules.softCap),
+        startTime: rules.startTime.toNumber(),
+        endTime: rules.endTime.toNumber(),
+        publicStartTime: rules.publicStartTime.toNumber(),
+        isAllowlistEnabled: rules.allowlistRoot.some((byte) => byte !== 0),
+        tgePercentage: rules.tgePercentage,
+        vestingDuration: rules.vestingDuration.toNumber(),
+        referralBonusPercentage: rules.referralBonusPercentage
+    }
+}
+
+// the phase decides which of buy, refund, release and claim the program accepts
+export async function getPresaleStatus() {
+    const [config, summary] = await Promise.all([
+        getPresaleConfigAccountData(),
+        getPresaleSummaryAccountData()
+    ])
+    if (!config || !summary) {
+        return null
+    }
+    const now = Math.floor(Date.now() / 1000)
+    return {
+        isStarted: now >= config.startTime,
+        isEnded: now >= config.endTime,
+        isAllowlistPhase: config.isAllowlistEnabled && now < config.publicStartTime,
+        isSoftCapReached: summary.tokenSold >= config.softCap,
+        isFundsReleased: summary.isFundsReleased
+    }
+}
+
+export async function getPresalePaymentMintAccountData(paymentMint: SolanaAddress) {
+    const result = await ElowenProgram.accounts.paymentMintAccount.fetchNullable(
+        findPaymentMintAccount(paymentMint)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        mint: result.mint,
+        decimals: result.decimals,
+        isEnabled: result.isEnabled,
+        raised: fromTokenFormat(result.raised, result.decimals),
+        tokenSold: fromTokenFormat(result.tokenSold),
+        refunded: fromTokenFormat(result.refunded, result.decimals),
+        sentToEda: fromTokenFormat(result.sentToEda, result.decimals),
+        isFundsReleased: result.isFundsReleased
+    }
+}
+
+export async function getPresalePaymentAccountData(
+    receiver: SolanaAddress,
+    paymentMint: SolanaAddress,
+    presaleType: PresaleType,
+    decimals: number
+) {
+    const result = await ElowenProgram.accounts.paymentAccount.fetchNullable(
+        findPresalePaymentAccount(receiver, paymentMint, presaleType)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        amount: fromTokenFormat(result.amount, decimals),
+        tokenBought: fromTokenFormat(result.tokenBought)
+    }
+}
+
+export async function getPresaleReceiptSummaryAccountData(presaleType: PresaleType) {
+    const result = await ElowenProgram.accounts.receiptSummaryAccount.fetchNullable(
+        findPresaleReceiptSummaryAccount(presaleType)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        mintedAmount: fromTokenFormat(result.mintedAmount),
+        burnedAmount: fromTokenFormat(result.burnedAmount),
+        redeemedAmount: fromTokenFormat(result.redeemedAmount)
+    }
+}
+
+export async function getUserPresaleReceiptBalance(
+    userWallet: SolanaAddress,
+    presaleType: PresaleType
+) {
+    const result = await getTokenAccountInfo(
+        getAssociatedTokenAddressSync(
+            findPresaleReceiptMint(presaleType),
+            maybeToPublicKey(userWallet)
+        )
+    )
+    const balance = result?.parsed.info.tokenAmount.uiAmount || 0
+    return {
+        amount: balance,
+        amountFormatted: formatNumber(balance)
+    }
+}
+
 export async function getPresaleVaultElwBalance() {
     const result = await getTokenAccountInfo(await getVaultAccountElwAta(VaultAccount.Presale))
     const presaleElw = result?.parsed.info.tokenAmount || { uiAmount: 0 }
@@ -1,63 +1,188 @@
 import ElowenProgram from '../program'
 import { getElwMint } from './platform'
-import { PublicKey, Signer, Transaction } from '@solana/web3.js'
+import { PublicKey, Transaction } from '@solana/web3.js'
 import { ClaimableReward, SolanaAddress, VaultAccount } from '../types'
 import {
     formatNumber,
     fromFormat,
     fromTokenFormat,
+    getMultisigVaultPda,
     getTokenAccountInfo,
-    getVaultAccount,
     getVaultAccountElwAta,
     maybeToPublicKey,
-    toBn,
-    toFormat
+    toTokenFormat
 } from '../utils'
 
+function findRewardPoolAccount(pool: number) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('reward_pool'), Buffer.from([pool])],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findRewardEpochAccount(pool: number, epoch: number) {
+    const epochBuffer = Buffer.alloc(4)
+    epochBuffer.writeUInt32LE(epoch)
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('reward_epoch'), Buffer.from([pool]), epochBuffer],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findUserRewardAccount(userWallet: SolanaAddress, pool: number) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('user_reward'), maybeToPublicKey(userWallet).toBuffer(), Buffer.from([pool])],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findRewardEpochTotalAccount(epoch: number) {
+    const epochBuffer = Buffer.alloc(4)
+    epochBuffer.writeUInt32LE(epoch)
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('reward_epoch_total'), epochBuffer],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+// shares are in basis points of the month emission, signed by the owner
+export async function createCreateRewardPoolInstruction(
+    pool: number,
+    sharePercentage: number,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .createRewardPool(pool, sharePercentage)
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .accountsPartial({
+            rewardPool: findRewardPoolAccount(pool)
+        })
+        .instruction()
+}
+
+export async function createCreateRewardPoolTransaction(
+    pool: number,
+    sharePercentage: number,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(
+        await createCreateRewardPoolInstruction(pool, sharePercentage, signer)
+    )
+}
+
+// a disabled pool gives its share back to the emission
+export async function createUpdateRewardPoolInstruction(
+    pool: number,
+    sharePercentage: number,
+    isEnabled: boolean,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return await ElowenProgram.methods
+        .updateRewardPool(sharePercentage, isEnabled)
+        .accounts({
+            signer: maybeToPublicKey(signer)
+        })
+        .accountsPartial({
+            rewardPool: findRewardPoolAccount(pool)
+        })
+        .instruction()
+}
+
+export async function createUpdateRewardPoolTransaction(
+    pool: number,
+    sharePercentage: number,
+    isEnabled: boolean,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(
+        await createUpdateRewardPoolInstruction(pool, sharePercentage, isEnabled, signer)
+    )
+}
+
+export async function createPublishRewardEpochInstruction(
+    distributor: SolanaAddress,
+    pool: number,
+    epoch: number,
+    merkleRoot: Buffer,
+    totalAmount: number,
+    leafCount: number
+) {
+    return await ElowenProgram.methods
+        .publishRewardEpoch(epoch, Array.from(merkleRoot), toTokenFormat(totalAmount), leafCount)
+        .accounts({
+            signer: maybeToPublicKey(distributor)
+        })
+        .accountsPartial({
+            rewardPool: findRewardPoolAccount(pool),
+            rewardEpoch: findRewardEpochAccount(pool, epoch),
+            rewardEpochTotal: findRewardEpochTotalAccount(epoch)
+        })
+        .instruction()
+}
+
+export async function createPublishRewardEpochTransaction(
+    distributor: SolanaAddress,
+    pool: number,
+    epoch: number,
+    merkleRoot: Buffer,
+    totalAmount: number,
+    leafCount: number
+) {
+    return new Transaction().add(
+        await createPublishRewardEpochInstruction(
+            distributor,
+            pool,
+            epoch,
+            merkleRoot,
+            totalAmount,
+            leafCount
+        )
+    )
+}
+
 export async function createClaimElwRewardInstruction(
+    payer: SolanaAddress,
     userWallet: SolanaAddress,
-    claimableRewards: ClaimableReward[]
+    claimableReward: ClaimableReward
 ) {
     const [elwMint, rewardTokenAta] = await Promise.all([
         getElwMint(),
         getVaultAccountElwAta(VaultAccount.Reward)
     ])
-    let ataAuthority: PublicKey
-    if (maybeToPublicKey(userWallet).equals(ElowenProgram.provider.wallet.publicKey)) {
-        ataAuthority = getVaultAccount(VaultAccount.Platform)
-    } else {
-        ataAuthority = maybeToPublicKey(userWallet)
-    }
-    const instruction = await ElowenProgram.methods
+    return await ElowenProgram.methods
         .claimElwReward(
-            claimableRewards.map((reward) => ({
-                timestamp: toBn(reward.timestamp),
-                percentage: toFormat(reward.percentage)
-            }))
+            claimableReward.index,
+            toTokenFormat(claimableReward.amount),
+            claimableReward.proof.map((node) => Array.from(node))
         )
         .accounts({
             elwMint,
-            ataAuthority,
             rewardTokenAta,
-            signer: ElowenProgram.wallet.publicKey,
+            payer: maybeToPublicKey(payer),
             receiver: maybeToPublicKey(userWallet)
         })
-        .signers([ElowenProgram.wallet.payer])
+        .accountsPartial({
+            rewardEpoch: findRewardEpochAccount(claimableReward.pool, claimableReward.epoch),
+            rewardPool: findRewardPoolAccount(claimableReward.pool),
+            userRewardAccount: findUserRewardAccount(userWallet, claimableReward.pool)
+        })
         .instruction()
-
-    return { instruction, signerWallet: ElowenProgram.wallet.payer as Signer }
 }
 
 export async function createClaimElwRewardTransaction(
+    payer: SolanaAddress,
     userWallet: SolanaAddress,
-    claimableRewards: ClaimableReward[]
+    claimableReward: ClaimableReward
 ) {
-    const { instruction, signerWallet } = await createClaimElwRewardInstruction(
-        userWallet,
-        claimableRewards
+    return new Transaction().add(
+        await createClaimElwRewardInstruction(payer, userWallet, claimableReward)
     )
-    const transaction = new Transaction().add(instruction)
-    return { transaction, signerWallet }
 }
 
 export async function getRewardVaultElwBalance() {
@@ -84,3 +209,46 @@ export async function getRewardAccountData(userWallet: SolanaAddress) {
         percentage: fromFormat(result.percentage)
     }
 }
+
+export async function getRewardPoolAccountData(pool: number) {
+    const result = await ElowenProgram.accounts.rewardPoolAccount.fetchNullable(
+        findRewardPoolAccount(pool)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        id: result.id,
+        sharePercentage: result.sharePercentage,
+        isEnabled: result.isEnabled,
+        publishedAmount: fromTokenFormat(result.publishedAmount),
+        claimedAmount: fromTokenFormat(result.claimedAmount)
+    }
+}
+
+export async function getRewardPoolsAccountData() {
+    const result = await ElowenProgram.accounts.rewardPoolsAccount.fetchNullable(
+        PublicKey.findProgramAddressSync([Buffer.from('reward_pools')], ElowenProgram.ID)[0]
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        totalSharePercentage: result.totalSharePercentage
+    }
+}
+
+export async function getUserRewardAccountData(userWallet: SolanaAddress, pool: number) {
+    const result = await ElowenProgram.accounts.userRewardAccount.fetchNullable(
+        findUserRewardAccount(userWallet, pool)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        receiver: result.receiver,
+        pool: result.pool,
+        claimedAmount: fromTokenFormat(result.claimedAmount),
+        lastClaimTime: result.lastClaimTime.toNumber()
+    }
+}
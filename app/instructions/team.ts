@@ -1,16 +1,130 @@
 import ElowenProgram from '../program'
 import { getElwMint } from './platform'
-import { VaultAccount, SolanaAddress } from '../types'
 import { PublicKey, Transaction } from '@solana/web3.js'
 import { getAssociatedTokenAddressSync } from '@solana/spl-token'
+import { VaultAccount, SolanaAddress, VestingSchedule } from '../types'
 import {
     formatNumber,
     fromTokenFormat,
+    getMultisigVaultPda,
     getTokenAccountInfo,
     getVaultAccountElwAta,
-    maybeToPublicKey
+    maybeToPublicKey,
+    toTokenFormat,
+    toVestingScheduleFormat
 } from '../utils'
 
+function findTeamVestingAccount(teamMember: SolanaAddress) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('team_vesting'), maybeToPublicKey(teamMember).toBuffer()],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+function findMemberClaimAccount(teamMember: SolanaAddress) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('member'), maybeToPublicKey(teamMember).toBuffer()],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+// signed by the multisig vault, which owns the admin config
+export async function createCreateTeamVestingInstruction(
+    teamMember: SolanaAddress,
+    totalAmount: number,
+    schedule: VestingSchedule,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    // the claim progress is only reset when the member claimed before
+    const memberClaim = findMemberClaimAccount(teamMember)
+    const memberClaimInfo = await ElowenProgram.connection.getAccountInfo(memberClaim)
+    return await ElowenProgram.methods
+        .createTeamVesting(toTokenFormat(totalAmount), toVestingScheduleFormat(schedule))
+        .accounts({
+            signer: maybeToPublicKey(signer),
+            member: maybeToPublicKey(teamMember)
+        })
+        .accountsPartial({
+            teamVesting: findTeamVestingAccount(teamMember),
+            memberClaim: memberClaimInfo ? memberClaim : null
+        })
+        .instruction()
+}
+
+export async function createCreateTeamVestingTransaction(
+    teamMember: SolanaAddress,
+    totalAmount: number,
+    schedule: VestingSchedule,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(
+        await createCreateTeamVestingInstruction(teamMember, totalAmount, schedule, signer)
+    )
+}
+
+// unvested tokens move to the EDA, vested ones stay claimable by the member
+export async function createRevokeTeamVestingInstruction(
+    teamMember: SolanaAddress,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    const [elwMint, teamTokenAta, edaTokenAta] = await Promise.all([
+        getElwMint(),
+        getVaultAccountElwAta(VaultAccount.Team),
+        getVaultAccountElwAta(VaultAccount.Eda)
+    ])
+    return await ElowenProgram.methods
+        .revokeTeamVesting()
+        .accounts({
+            elwMint,
+            signer: maybeToPublicKey(signer)
+        })
+        .accountsPartial({
+            teamTokenAta,
+            edaTokenAta,
+            teamVesting: findTeamVestingAccount(teamMember)
+        })
+        .instruction()
+}
+
+export async function createRevokeTeamVestingTransaction(
+    teamMember: SolanaAddress,
+    signer: SolanaAddress = getMultisigVaultPda()
+) {
+    return new Transaction().add(await createRevokeTeamVestingInstruction(teamMember, signer))
+}
+
+// moves the vesting and the claim progress of a lost wallet to a new one
+export async function createRotateTeamMemberInstruction(
+    teamMember: SolanaAddress,
+    newTeamMember: SolanaAddress
+) {
+    return await ElowenProgram.methods
+        .rotateTeamMember()
+        .accounts({
+            signer: getMultisigVaultPda(),
+            member: maybeToPublicKey(teamMember),
+            newMember: maybeToPublicKey(newTeamMember)
+        })
+        .accountsPartial({
+            teamVesting: findTeamVestingAccount(teamMember),
+            newTeamVesting: findTeamVestingAccount(newTeamMember),
+            memberClaim: findMemberClaimAccount(teamMember),
+            newMemberClaim: findMemberClaimAccount(newTeamMember)
+        })
+        .instruction()
+}
+
+export async function createRotateTeamMemberTransaction(
+    teamMember: SolanaAddress,
+    newTeamMember: SolanaAddress
+) {
+    return new Transaction().add(
+        await createRotateTeamMemberInstruction(teamMember, newTeamMember)
+    )
+}
+
 export async function createClaimTeamMemberElwInstruction(teamMember: SolanaAddress) {
     const [elwMint, teamTokenAta] = await Promise.all([
         getElwMint(),
@@ -24,6 +138,7 @@ export async function createClaimTeamMemberElwInstruction(teamMember: SolanaAddr
         })
         .accountsPartial({
             teamTokenAta,
+            teamVesting: findTeamVestingAccount(teamMember),
             memberTokenAta: getAssociatedTokenAddressSync(elwMint, maybeToPublicKey(teamMember))
         })
         .instruction()
@@ -33,12 +148,31 @@ export async function createClaimTeamMemberElwTransaction(teamMember: SolanaAddr
     return new Transaction().add(await createClaimTeamMemberElwInstruction(teamMember))
 }
 
+export async function getTeamVestingAccountData(teamMember: SolanaAddress) {
+    const result = await ElowenProgram.accounts.teamVestingAccount.fetchNullable(
+        findTeamVestingAccount(teamMember)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        member: result.member,
+        isRevoked: result.isRevoked,
+        totalAmount: fromTokenFormat(result.totalAmount),
+        schedule: {
+            startTime: result.schedule.startTime.toNumber(),
+            cliffTime: result.schedule.cliffTime.toNumber(),
+            endTime: result.schedule.endTime.toNumber(),
+            tgePercentage: result.schedule.tgePercentage
+        },
+        totalAmountFormatted: formatNumber(fromTokenFormat(result.totalAmount))
+    }
+}
+
 export async function getTeamMemberClaimAccountData(userWallet: SolanaAddress) {
-    const [pda, _bump] = PublicKey.findProgramAddressSync(
-        [Buffer.from('member'), maybeToPublicKey(userWallet).toBuffer()],
-        ElowenProgram.ID
+    const result = await ElowenProgram.accounts.memberClaimAccount.fetchNullable(
+        findMemberClaimAccount(userWallet)
     )
-    const result = await ElowenProgram.accounts.memberClaimAccount.fetchNullable(pda)
     if (!result) {
         return null
     }
@@ -0,0 +1,235 @@
+import ElowenProgram from '../program'
+import { BN } from '@coral-xyz/anchor'
+import { PublicKey, Transaction } from '@solana/web3.js'
+import { getAssociatedTokenAddressSync } from '@solana/spl-token'
+import { SolanaAddress, VaultAccount } from '../types'
+import {
+    WSOL_MINT,
+    fromTokenFormat,
+    getMultisigVaultPda,
+    getVaultAccount,
+    maybeToPublicKey,
+    toBn,
+    toTokenFormat,
+    vaultAccountToRustEnum
+} from '../utils'
+
+export function findSpendingLimitAccount(vault: VaultAccount, mint: SolanaAddress) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [Buffer.from('spending_limit'), Buffer.from(vault), maybeToPublicKey(mint).toBuffer()],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+export function findWithdrawalRequestAccount(spendingLimit: PublicKey, id: number) {
+    const [pda, _bump] = PublicKey.findProgramAddressSync(
+        [
+            Buffer.from('withdrawal_request'),
+            spendingLimit.toBuffer(),
+            new BN(id).toArrayLike(Buffer, 'le', 8)
+        ],
+        ElowenProgram.ID
+    )
+    return pda
+}
+
+// WSOL mint stands for SOL, periods and timelocks are in seconds
+export async function createSetSpendingLimitInstruction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    limitAmount: number,
+    period: number,
+    timelock: number,
+    decimals: number = 9
+) {
+    return await ElowenProgram.methods
+        .setSpendingLimit(
+            vaultAccountToRustEnum(vault),
+            maybeToPublicKey(mint),
+            toTokenFormat(limitAmount, decimals),
+            toBn(period),
+            toBn(timelock)
+        )
+        .accounts({
+            signer: getMultisigVaultPda()
+        })
+        .accountsPartial({
+            spendingLimit: findSpendingLimitAccount(vault, mint)
+        })
+        .instruction()
+}
+
+export async function createSetSpendingLimitTransaction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    limitAmount: number,
+    period: number,
+    timelock: number,
+    decimals: number = 9
+) {
+    return new Transaction().add(
+        await createSetSpendingLimitInstruction(
+            vault,
+            mint,
+            limitAmount,
+            period,
+            timelock,
+            decimals
+        )
+    )
+}
+
+// withdrawals above what is left of the limit wait for the timelock
+export async function createQueueWithdrawalInstruction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    receiver: SolanaAddress,
+    amount: number,
+    decimals: number = 9
+) {
+    const spendingLimit = findSpendingLimitAccount(vault, mint)
+    const { requestCount } = await ElowenProgram.accounts.spendingLimitAccount.fetch(spendingLimit)
+    return await ElowenProgram.methods
+        .queueWithdrawal(toTokenFormat(amount, decimals))
+        .accounts({
+            signer: getMultisigVaultPda(),
+            receiver: maybeToPublicKey(receiver)
+        })
+        .accountsPartial({
+            spendingLimit,
+            withdrawalRequest: findWithdrawalRequestAccount(spendingLimit, requestCount.toNumber())
+        })
+        .instruction()
+}
+
+export async function createQueueWithdrawalTransaction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    receiver: SolanaAddress,
+    amount: number,
+    decimals: number = 9
+) {
+    return new Transaction().add(
+        await createQueueWithdrawalInstruction(vault, mint, receiver, amount, decimals)
+    )
+}
+
+export async function createExecuteWithdrawalInstruction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    id: number
+) {
+    mint = maybeToPublicKey(mint)
+    const spendingLimit = findSpendingLimitAccount(vault, mint)
+    const withdrawalRequest = findWithdrawalRequestAccount(spendingLimit, id)
+    const { receiver } = await ElowenProgram.accounts.withdrawalRequestAccount.fetch(
+        withdrawalRequest
+    )
+
+    // SOL moves from the vault lamports, tokens through the program that owns the mint
+    const isSol = mint.equals(WSOL_MINT)
+    const tokenProgram = isSol
+        ? undefined
+        : (await ElowenProgram.connection.getAccountInfo(mint))?.owner
+    const vaultAccount = getVaultAccount(vault)
+    return await ElowenProgram.methods
+        .executeWithdrawal()
+        .accounts({
+            signer: getMultisigVaultPda(),
+            ...(tokenProgram && { tokenProgram })
+        })
+        .accountsPartial({
+            receiver,
+            spendingLimit,
+            withdrawalRequest,
+            vault: vaultAccount,
+            mint: isSol ? null : mint,
+            vaultTokenAta: isSol
+                ? null
+                : getAssociatedTokenAddressSync(mint, vaultAccount, true, tokenProgram),
+            receiverTokenAta: isSol
+                ? null
+                : getAssociatedTokenAddressSync(mint, receiver, true, tokenProgram)
+        })
+        .instruction()
+}
+
+export async function createExecuteWithdrawalTransaction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    id: number
+) {
+    return new Transaction().add(await createExecuteWithdrawalInstruction(vault, mint, id))
+}
+
+export async function createCancelWithdrawalInstruction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    id: number
+) {
+    const spendingLimit = findSpendingLimitAccount(vault, mint)
+    return await ElowenProgram.methods
+        .cancelWithdrawal()
+        .accounts({
+            signer: getMultisigVaultPda()
+        })
+        .accountsPartial({
+            withdrawalRequest: findWithdrawalRequestAccount(spendingLimit, id)
+        })
+        .instruction()
+}
+
+export async function createCancelWithdrawalTransaction(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    id: number
+) {
+    return new Transaction().add(await createCancelWithdrawalInstruction(vault, mint, id))
+}
+
+export async function getSpendingLimitAccountData(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    decimals: number = 9
+) {
+    const result = await ElowenProgram.accounts.spendingLimitAccount.fetchNullable(
+        findSpendingLimitAccount(vault, mint)
+    )
+    if (!result) {
+        return null
+    }
+    // withdrawals of the current rolling period
+    const periodStart = Math.floor(Date.now() / 1000) - result.period.toNumber()
+    const spentAmount = result.spends
+        .filter((spend) => spend.timestamp.toNumber() > periodStart)
+        .reduce((total, spend) => total.add(spend.amount), new BN(0))
+    return {
+        mint: result.mint,
+        limitAmount: fromTokenFormat(result.limitAmount, decimals),
+        period: result.period.toNumber(),
+        timelock: result.timelock.toNumber(),
+        requestCount: result.requestCount.toNumber(),
+        spentAmount: fromTokenFormat(spentAmount, decimals)
+    }
+}
+
+export async function getWithdrawalRequestAccountData(
+    vault: VaultAccount,
+    mint: SolanaAddress,
+    id: number,
+    decimals: number = 9
+) {
+    const result = await ElowenProgram.accounts.withdrawalRequestAccount.fetchNullable(
+        findWithdrawalRequestAccount(findSpendingLimitAccount(vault, mint), id)
+    )
+    if (!result) {
+        return null
+    }
+    return {
+        id: result.id.toNumber(),
+        receiver: result.receiver,
+        amount: fromTokenFormat(result.amount, decimals),
+        executeAfter: result.executeAfter.toNumber()
+    }
+}
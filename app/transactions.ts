@@ -22,8 +22,7 @@ import {
     getVaultAccountTokenAtaByMint,
     presaleTypeFromRustEnum,
     swapDirectionFromRustEnum,
-    vaultAccountFromRustEnum,
-    WSOL_MINT
+    vaultAccountFromRustEnum
 } from './utils'
 
 export type TransactionsFilter = {
@@ -98,9 +97,8 @@ export interface TransactionDetails {
         paidAmount: number
         currency: Currency
         receivedAmount: number
-        transferredToEda: number
         presaleType: PresaleType
-        transferredToLiquidity: number
+        transferredToEscrow: number
     }
     claim_presale_elw: {
         claimedAmount: number
@@ -254,6 +252,7 @@ async function transactionDetails<T extends TransactionType>(
         const platformVault = getVaultAccount(VaultAccount.Platform)
         const treasuryVault = getVaultAccount(VaultAccount.Treasury)
         const liquidityVault = getVaultAccount(VaultAccount.Liquidity)
+        const presaleEscrow = getVaultAccount(VaultAccount.PresaleEscrow)
         const edaElwAta = await getVaultAccountElwAta(VaultAccount.Eda)
 
         switch (type) {
@@ -480,50 +479,35 @@ async function transactionDetails<T extends TransactionType>(
                 }
                 break
             case 'buy_presale_elw':
-                let transferredToEda = 0
-                let transferredToLiquidity = 0
+                // payments stay in the presale escrow until release or refund
+                let transferredToEscrow = 0
                 const buyPresaleCurrency = currencyFromRustEnum(decodedInstruction.currency)
                 if (buyPresaleCurrency === Currency.SOL) {
-                    const buyPresaleSolIxs = innerInstructions.filter(
-                        (ix) => ix.parsed.type === 'transfer' && ix.program == 'system'
-                    )
-                    const buyPresaleEdaIx = buyPresaleSolIxs.find((ix) =>
-                        new PublicKey(ix.parsed.info.destination).equals(edaVault)
-                    )
-                    const buyPresaleLiquidityIx = buyPresaleSolIxs.find((ix) =>
-                        new PublicKey(ix.parsed.info.destination).equals(
-                            getVaultAccountTokenAtaByMint(VaultAccount.Liquidity, WSOL_MINT)
-                        )
-                    )
-                    transferredToEda = fromTokenFormat(buyPresaleEdaIx.parsed.info.lamports)
-                    transferredToLiquidity = fromTokenFormat(
-                        buyPresaleLiquidityIx.parsed.info.lamports
+                    const buyPresaleEscrowIx = innerInstructions.find(
+                        (ix) =>
+                            ix.parsed?.type === 'transfer' &&
+                            ix.program == 'system' &&
+                            new PublicKey(ix.parsed.info.destination).equals(presaleEscrow)
                     )
+                    transferredToEscrow = fromTokenFormat(buyPresaleEscrowIx.parsed.info.lamports)
                 } else {
-                    const buyPresaleUsdcIxs = innerInstructions.filter(
-                        (ix) => ix.parsed.type === 'transfer' && ix.program == 'spl-token'
-                    )
-                    const buyPresaleEdaIx = buyPresaleUsdcIxs.find((ix) =>
-                        new PublicKey(ix.parsed.info.destination).equals(
-                            getVaultAccountTokenAtaByMint(VaultAccount.Eda, getUsdcMint())
-                        )
-                    )
-                    const buyPresaleLiquidityIx = buyPresaleUsdcIxs.find((ix) =>
-                        new PublicKey(ix.parsed.info.destination).equals(
-                            getVaultAccountTokenAtaByMint(VaultAccount.Liquidity, getUsdcMint())
-                        )
-                    )
-                    transferredToEda = fromTokenFormat(buyPresaleEdaIx.parsed.info.amount, 6)
-                    transferredToLiquidity = fromTokenFormat(
-                        buyPresaleLiquidityIx.parsed.info.amount,
-                        6
+                    const buyPresaleEscrowIx = innerInstructions.find(
+                        (ix) =>
+                            ix.parsed?.type === 'transfer' &&
+                            ix.program == 'spl-token' &&
+                            new PublicKey(ix.parsed.info.destination).equals(
+                                getVaultAccountTokenAtaByMint(
+                                    VaultAccount.PresaleEscrow,
+                                    getUsdcMint()
+                                )
+                            )
                     )
+                    transferredToEscrow = fromTokenFormat(buyPresaleEscrowIx.parsed.info.amount, 6)
                 }
                 details = {
-                    transferredToEda,
-                    transferredToLiquidity,
+                    transferredToEscrow,
                     currency: buyPresaleCurrency,
-                    paidAmount: transferredToEda + transferredToLiquidity,
+                    paidAmount: transferredToEscrow,
                     receivedAmount: fromTokenFormat(decodedInstruction.amount_to_buy),
                     presaleType: presaleTypeFromRustEnum(decodedInstruction.presale_type)
                 }
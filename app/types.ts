@@ -9,44 +9,87 @@ export enum ErrorCode {
     Unauthorized = 'Unauthorized',
     AllTokensSold = 'AllTokensSold',
     PresaleIsEnded = 'PresaleIsEnded',
+    SoftCapReached = 'SoftCapReached',
+    NotInAllowlist = 'NotInAllowlist',
     InvalidCurrency = 'InvalidCurrency',
     PdaAlreadyInUse = 'PdaAlreadyInUse',
     NoRewardInVault = 'NoRewardInVault',
+    InvalidReceiver = 'InvalidReceiver',
+    NothingToRefund = 'NothingToRefund',
+    InvalidReferrer = 'InvalidReferrer',
     ExceededSlippage = 'ExceededSlippage',
     PeriodNotReached = 'PeriodNotReached',
+    VestingIsRevoked = 'VestingIsRevoked',
     AllRewardsClaimed = 'AllRewardsClaimed',
     PresaleIsNotEnded = 'PresaleIsNotEnded',
+    SoftCapNotReached = 'SoftCapNotReached',
+    InvalidRewardPool = 'InvalidRewardPool',
+    PoolAlreadyCreated = 'PoolAlreadyCreated',
     WrongAccountGiven = 'WrongAccountGiven',
     ZeroTradingTokens = 'ZeroTradingTokens',
     InsufficientReward = 'InsufficientReward',
+    InvalidMerkleProof = 'InvalidMerkleProof',
     NoClaimableRewards = 'NoClaimableRewards',
+    TimelockNotElapsed = 'TimelockNotElapsed',
     InsufficientBalance = 'InsufficientBalance',
     MemberShareNotFound = 'MemberShareNotFound',
     PresaleIsNotStarted = 'PresaleIsNotStarted',
+    VestingIsNotRevoked = 'VestingIsNotRevoked',
     TokensAlreadyClaimed = 'TokensAlreadyClaimed',
+    InvalidSpendingLimit = 'InvalidSpendingLimit',
     AccountNotInitialized = 'AccountNotInitialized',
     InsufficientLiquidity = 'InsufficientLiquidity',
+    VestedTokensNotClaimed = 'VestedTokensNotClaimed',
+    InvalidVestingSchedule = 'InvalidVestingSchedule',
     NotEnoughBalanceInVault = 'NotEnoughBalanceInVault',
+    LiquidityOwnedByPresale = 'LiquidityOwnedByPresale',
     ClaimableRewardNotReady = 'ClaimableRewardNotReady',
+    InvalidWithdrawalAmount = 'InvalidWithdrawalAmount',
+    ExceedsTheSpendingLimit = 'ExceedsTheSpendingLimit',
+    InvalidEmissionSchedule = 'InvalidEmissionSchedule',
     ExceededTransactionLimit = 'ExceededTransactionLimit',
+    ExceedsTheMaximumPayment = 'ExceedsTheMaximumPayment',
+    ExceedsTheTeamAllocation = 'ExceedsTheTeamAllocation',
     UnsoldTokensAlreadyBurned = 'UnsoldTokensAlreadyBurned',
     ExceedsTheRemainingAmount = 'ExceedsTheRemainingAmount',
     CannotBurnUntilPresaleDone = 'CannotBurnUntilPresaleDone',
     CannotClaimUntilUnlockTime = 'CannotClaimUntilUnlockTime',
     AlreadyClaimedForThisPeriod = 'AlreadyClaimedForThisPeriod',
     BelowTheMinimumContribution = 'BelowTheMinimumContribution',
+    PresaleFundsAlreadyReleased = 'PresaleFundsAlreadyReleased',
     ExceedsTheMaximumContribution = 'ExceedsTheMaximumContribution',
+    SummaryAccountAlreadyMigrated = 'SummaryAccountAlreadyMigrated',
+    ExceedsTheAllowlistAllocation = 'ExceedsTheAllowlistAllocation',
     CannotInitializeUntilPresaleDone = 'CannotInitializeUntilPresaleDone',
     ThisAmountIsLockedForMiningRewards = 'ThisAmountIsLockedForMiningRewards'
 }
 
 export type ClaimableReward = {
-    timestamp: number
-    percentage: number
+    pool: number
+    epoch: number
+    index: number
+    amount: number
+    proof: Buffer[]
 }
 
 export type SolanaAddress = string | PublicKey
 
+// unix seconds, the TGE share is in basis points
+export type VestingSchedule = {
+    startTime: number
+    cliffTime: number
+    endTime: number
+    tgePercentage: number
+}
+
+// role keys checked by the program, the owner manages them
+export type AdminRoles = {
+    operator: SolanaAddress
+    treasurer: SolanaAddress
+    rewardDistributor: SolanaAddress
+    liquidityManager: SolanaAddress
+}
+
 export enum Currency {
     USDC = 'USDC',
     SOL = 'SOL',
@@ -81,6 +124,11 @@ export enum PresaleTypeMap {
     SixMonthsLockup = 2
 }
 
+export type AllowlistProof = {
+    allocation: number
+    proof: number[][]
+}
+
 export type BuyPremiumEvent = {
     buyer: PublicKey
     amount: number
@@ -90,11 +138,14 @@ export type BuyPremiumEvent = {
 export type BuyPresaleTokenEvent = {
     receiver: PublicKey
     amount: number
+    round: number
 }
 
 export type ClaimRewardEvent = {
     receiver: PublicKey
     amount: number
+    pool: number
+    epoch: number
 }
 
 export type ElwBurnEvent = {
@@ -107,6 +158,22 @@ export enum SwapDirection {
     Output
 }
 
+export enum EmissionCurve {
+    Halving,
+    LinearDecay,
+    Steps
+}
+
+// monthly ELW emission, months are counted from the presale end
+export type EmissionSchedule = {
+    curve: EmissionCurve
+    baseAmount: number
+    halvingMonths: number
+    decayAmount: number
+    minAmount: number
+    steps: { fromMonth: number; amount: number }[]
+}
+
 export enum RoundDirection {
     Floor,
     Ceiling
@@ -119,7 +186,8 @@ export enum VaultAccount {
     Presale = 'presale',
     Treasury = 'treasury',
     Liquidity = 'liquidity',
-    Platform = 'platform'
+    Platform = 'platform',
+    PresaleEscrow = 'presale_escrow'
 }
 
 export type RaydiumKeyNft = {
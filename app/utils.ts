@@ -6,15 +6,19 @@ import { ParsedAccountData, PublicKey } from '@solana/web3.js'
 import { getAssociatedTokenAddressSync, NATIVE_MINT } from '@solana/spl-token'
 
 import {
+    AdminRoles,
     Currency,
     CurrencyMap,
+    EmissionCurve,
+    EmissionSchedule,
     PresaleType,
     PresaleTypeMap,
     SolanaAddress,
     IDLType,
     QuoteCurrency,
     VaultAccount,
-    SwapDirection
+    SwapDirection,
+    VestingSchedule
 } from './types'
 import { getVaultPda } from '@sqds/multisig'
 
@@ -60,6 +64,64 @@ export function toTokenFormat(amount: number, decimals: number = 9) {
     }
 }
 
+export function toVestingScheduleFormat(schedule: VestingSchedule) {
+    return {
+        startTime: toBn(schedule.startTime),
+        cliffTime: toBn(schedule.cliffTime),
+        endTime: toBn(schedule.endTime),
+        tgePercentage: schedule.tgePercentage
+    }
+}
+
+export function toAdminRolesFormat(roles: AdminRoles) {
+    return {
+        operator: maybeToPublicKey(roles.operator),
+        treasurer: maybeToPublicKey(roles.treasurer),
+        rewardDistributor: maybeToPublicKey(roles.rewardDistributor),
+        liquidityManager: maybeToPublicKey(roles.liquidityManager)
+    }
+}
+
+export function emissionCurveToRustEnum(curve: EmissionCurve): IdlTypes<IDLType>['emissionCurve'] {
+    switch (curve) {
+        case EmissionCurve.Halving:
+            return { halving: {} }
+        case EmissionCurve.LinearDecay:
+            return { linearDecay: {} }
+        case EmissionCurve.Steps:
+            return { steps: {} }
+    }
+}
+
+export function emissionCurveFromRustEnum(
+    curve: IdlTypes<IDLType>['emissionCurve']
+): EmissionCurve {
+    const keys = Object.keys(curve).map((k) => k.toLowerCase())
+    if (keys.includes('halving')) {
+        return EmissionCurve.Halving
+    } else if (keys.includes('lineardecay')) {
+        return EmissionCurve.LinearDecay
+    } else if (keys.includes('steps')) {
+        return EmissionCurve.Steps
+    } else {
+        throw new Error('Invalid emission curve')
+    }
+}
+
+export function toEmissionScheduleFormat(schedule: EmissionSchedule) {
+    return {
+        curve: emissionCurveToRustEnum(schedule.curve),
+        baseAmount: toTokenFormat(schedule.baseAmount),
+        halvingMonths: schedule.halvingMonths,
+        decayAmount: toTokenFormat(schedule.decayAmount),
+        minAmount: toTokenFormat(schedule.minAmount),
+        steps: schedule.steps.map((step) => ({
+            fromMonth: step.fromMonth,
+            amount: toTokenFormat(step.amount)
+        }))
+    }
+}
+
 export function fromTokenFormat(amount: BN | string | number, decimals: number = 9) {
     if (!(amount instanceof BN)) {
         amount = new BN(amount)
@@ -172,6 +234,8 @@ export function vaultAccountToRustEnum(vault: VaultAccount): IdlTypes<IDLType>['
             return { platform: {} }
         case VaultAccount.Liquidity:
             return { liquidity: {} }
+        case VaultAccount.PresaleEscrow:
+            return { presaleEscrow: {} }
     }
 }
 
@@ -187,6 +251,8 @@ export function vaultAccountFromRustEnum(vault: IdlTypes<IDLType>['vaultAccount'
         return VaultAccount.Reward
     } else if (keys.includes('presale')) {
         return VaultAccount.Presale
+    } else if (keys.includes('presaleescrow')) {
+        return VaultAccount.PresaleEscrow
     } else if (keys.includes('treasury')) {
         return VaultAccount.Treasury
     } else if (keys.includes('eda')) {
@@ -1,10 +1,5 @@
 use anchor_lang::prelude::*;
 
-use crate::{enums::*, functions::*};
-
-// reward calculation start time
-pub const BASE_REWARD: u64 = 62_500_000 * 10u64.pow(9); // 62.5M tokens for first period
-
 // token metadata
 pub const SYMBOL: &str = "ELW";
 pub const NAME: &str = "Elowen";
@@ -13,53 +8,23 @@ pub const SUPPLY: u64 = 1_000_000_000 * 10u64.pow(9);
 // WSOL mint for using SOL in the program processes
 pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
 
-// The signer wallet is to prevent unauthorized access to some methods with incorrect data.
-// For example, in reward distribution
-// So it's the wallet that the platform uses to access the contract basically, but there are no funds.
-// All funds will be held in PDAs on the program at all times.
-#[cfg(feature = "devnet")]
-pub const SIGNER: Pubkey = pubkey!("9HGJSAC4HAwtQEpGSDNSWxDsksBVUFHVDUnu5JbhVwnK");
-#[cfg(not(feature = "devnet"))]
-pub const SIGNER: Pubkey = pubkey!("9HGJSAC4HAwtQEpGSDNSWxDsksBVUFHVDUnu5JbhVwnK");
+// The multisig initializes the admin config and becomes its first owner.
+// All other authorization is checked against the roles in the admin config.
 #[cfg(feature = "devnet")]
 pub const MULTISIG: Pubkey = pubkey!("Fp4bFEeAxRSDiZZzDBUWLVmfGxdLzDCNCviihLK51hCf");
 #[cfg(not(feature = "devnet"))]
 pub const MULTISIG: Pubkey = pubkey!("Fp4bFEeAxRSDiZZzDBUWLVmfGxdLzDCNCviihLK51hCf");
 
+// Pyth SOL/USD price feed
+pub const SOL_USD_FEED_ID: &str =
+    "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
 // Payment token USDC in mainnet and Test USDC in devnet
 #[cfg(feature = "devnet")]
 pub const USDC_MINT: Pubkey = pubkey!("28zvdJE2BwGLMeqtP1punErLRE38rE2qM7uvVAnXBKaL");
 #[cfg(not(feature = "devnet"))]
 pub const USDC_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
 
-#[cfg(feature = "devnet")]
-pub const TEAM_WALLETS: &[(Pubkey, u16)] = &[
-    (
-        pubkey!("9HGJSAC4HAwtQEpGSDNSWxDsksBVUFHVDUnu5JbhVwnK"),
-        4300,
-    ),
-    (
-        pubkey!("9AVAef1rAuyhzyjJxquUBjEgWn9zyoN4ZRZuSaibSaEv"),
-        3700,
-    ),
-    (pubkey!("5QvcwUs3DkxcY1FHj7hjSFcrYyvXhhXkpFSGk94PLkV"), 2000),
-];
-#[cfg(not(feature = "devnet"))]
-pub const TEAM_WALLETS: &[(Pubkey, u16)] = &[
-    (
-        pubkey!("EXPjTRuSHDSMxzxd8UbhcaktyPBn1Eg3ZZFJKn77zrp2"),
-        4300,
-    ),
-    (
-        pubkey!("7XeTie8StTYcH4XDePKJ6Gjz24o5RurYX2DU2uWFpnk6"),
-        3700,
-    ),
-    (
-        pubkey!("5n7c3o6cS9zjt3c6yDNDi3eXBRFoRitunaQuLMmEyqcW"),
-        2000,
-    ),
-];
-
 // all percentages
 pub const EDA_PERCENTAGE: u16 = 1000;
 pub const TEAM_PERCENTAGE: u16 = 1000;
@@ -72,124 +37,26 @@ pub const COLLECT_FEE_BURN_PERCENTAGE: u16 = 2500;
 pub const MINING_YEARLY_ELW_REWARD_PERCENTAGE: u16 = 2500; // Dynamic APR
 pub const MINING_YEARLY_ELW_REWARD_MAX_PERCENTAGE: u16 = 8000; // Max APR
 
-pub struct PresaleRules {
-    pub three_months_lockup_price: u64,
-    pub six_months_lockup_price: u64,
-    pub min_contribution: u64,
-    pub max_contribution: u64,
-    pub total_amount: u64,
-    pub start_time: i64,
-    pub end_time: i64,
-}
-
-// 3 months lockup presale price USD
-// 6 months lockup presale price USD
-// %0.1 of presale supply
-// %2 of presale supply
-// 10% of presale earnings
-// 2025-10-15 00:00:00
-// 2025-12-15 00:00:00
-pub const PRESALE_RULES: PresaleRules = PresaleRules {
-    three_months_lockup_price: (0.008 * 1000000.0) as u64,
-    six_months_lockup_price: (0.004 * 1000000.0) as u64,
-    min_contribution: 1000 * 10u64.pow(9),
-    max_contribution: 2_000_000 * 10u64.pow(9),
-    total_amount: 100_000_000 * 10u64.pow(9),
-    start_time: 1746940053,
-    end_time: 1765756800,
-};
-
-impl PresaleRules {
-    pub fn get_remaining_amount(&self, token_sold: u64) -> u64 {
-        self.total_amount - token_sold
-    }
-
-    pub fn is_presale_active(&self) -> bool {
-        self.is_presale_started() && !self.is_presale_ended()
-    }
-
-    pub fn is_presale_ended(&self) -> bool {
-        let clock = Clock::get().unwrap();
-        clock.unix_timestamp >= self.end_time
-    }
-
-    pub fn is_presale_started(&self) -> bool {
-        let clock = Clock::get().unwrap();
-        clock.unix_timestamp >= self.start_time
-    }
-
-    pub fn conditions<'info>(
-        &self,
-        amount_to_buy: u64,
-        token_sold: u64,
-        purchase_amount: u64,
-    ) -> Result<()> {
-        let remaining_amount = self.get_remaining_amount(token_sold);
-
-        require!(remaining_amount > 0, CustomError::AllTokensSold);
-
-        require!(self.is_presale_started(), CustomError::PresaleIsNotStarted);
-        require!(self.is_presale_active(), CustomError::PresaleIsEnded);
-
-        require!(
-            amount_to_buy <= remaining_amount,
-            CustomError::ExceedsTheRemainingAmount
-        );
-
-        let min_contribution = if remaining_amount > self.min_contribution {
-            self.min_contribution
-        } else {
-            remaining_amount
-        };
-
-        require!(
-            amount_to_buy >= min_contribution,
-            CustomError::BelowTheMinimumContribution
-        );
-
-        require!(
-            (amount_to_buy + purchase_amount) <= self.max_contribution,
-            CustomError::ExceedsTheMaximumContribution
-        );
+// presale rounds limit (seed, private, public...)
+pub const MAX_PRESALE_ROUNDS: usize = 5;
+pub const MAX_GRANT_MILESTONES: usize = 10;
 
-        Ok(())
-    }
+// withdrawals kept per spending limit for its rolling period
+pub const MAX_SPENDING_RECORDS: usize = 32;
 
-    pub fn get_unlock_time(&self, presale_type: PresaleType) -> i64 {
-        match presale_type {
-            PresaleType::ThreeMonthsLockup => get_months_later(self.end_time, 3),
-            PresaleType::SixMonthsLockup => get_months_later(self.end_time, 6),
-        }
-    }
+// reward epoch receivers limit, one claimed bit each
+pub const MAX_REWARD_EPOCH_RECEIVERS: u32 = 8192;
+pub const REWARD_EPOCH_BITMAP_LEN: usize = MAX_REWARD_EPOCH_RECEIVERS as usize / 8;
 
-    pub fn get_price_per_token(&self, presale_type: PresaleType) -> u64 {
-        match presale_type {
-            PresaleType::ThreeMonthsLockup => self.three_months_lockup_price,
-            PresaleType::SixMonthsLockup => self.six_months_lockup_price,
-        }
-    }
+// reward epochs limit, one claimed bit each per receiver
+pub const MAX_REWARD_EPOCHS: u32 = 256;
+pub const USER_REWARD_BITMAP_LEN: usize = MAX_REWARD_EPOCHS as usize / 8;
 
-    pub fn calculate_payment_amount(&self, amount_to_buy: u64, presale_type: PresaleType) -> u64 {
-        let price_per_token = self.get_price_per_token(presale_type);
-        (amount_to_buy as u128)
-            .checked_mul(price_per_token as u128)
-            .unwrap()
-            .checked_div(10u128.pow(9 - 6))
-            .unwrap()
-            .checked_div(10u128.pow(6))
-            .unwrap() as u64
-    }
+// reward pools limit, one per activity category
+pub const MAX_REWARD_POOLS: u8 = 8;
 
-    pub fn calculate_payment_amount_and_eda_amount(
-        &self,
-        amount_to_buy: u64,
-        presale_type: PresaleType,
-    ) -> (u64, u64) {
-        let payment_amount = self.calculate_payment_amount(amount_to_buy, presale_type);
-        let eda_amount = calculate_by_percentage(payment_amount, EDA_PERCENTAGE);
-        ((payment_amount - eda_amount), eda_amount)
-    }
-}
+// emission schedule custom steps limit
+pub const MAX_EMISSION_STEPS: usize = 24;
 
 // metadata account size
 pub const MAX_METADATA_LEN: usize = 607;
@@ -56,6 +56,86 @@ pub enum CustomError {
     InsufficientReward,
     #[msg("Member share not found")]
     MemberShareNotFound,
+    #[msg("Invalid presale config")]
+    InvalidPresaleConfig,
+    #[msg("Presale config is frozen")]
+    PresaleConfigIsFrozen,
+    #[msg("No active presale round")]
+    NoActivePresaleRound,
+    #[msg("Purchase account already migrated")]
+    PurchaseAccountAlreadyMigrated,
+    #[msg("Soft cap is not reached")]
+    SoftCapNotReached,
+    #[msg("Soft cap is reached")]
+    SoftCapReached,
+    #[msg("Presale funds already released")]
+    PresaleFundsAlreadyReleased,
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+    #[msg("Not in allowlist")]
+    NotInAllowlist,
+    #[msg("Exceeds the allowlist allocation")]
+    ExceedsTheAllowlistAllocation,
+    #[msg("Price is stale")]
+    StalePrice,
+    #[msg("Price confidence is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Invalid price")]
+    InvalidPrice,
+    #[msg("Wrong account given")]
+    WrongAccountGiven,
+    #[msg("This mint can not be used as a payment mint")]
+    InvalidPaymentMint,
+    #[msg("Payment mint is disabled")]
+    PaymentMintIsDisabled,
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+    #[msg("Payment amount exceeds the maximum payment amount")]
+    ExceedsTheMaximumPayment,
+    #[msg("Presale funds are not released yet")]
+    PresaleFundsNotReleased,
+    #[msg("Invalid receiver")]
+    InvalidReceiver,
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    #[msg("Vesting is revoked")]
+    VestingIsRevoked,
+    #[msg("Exceeds the team allocation")]
+    ExceedsTheTeamAllocation,
+    #[msg("Vesting is not revocable")]
+    VestingIsNotRevocable,
+    #[msg("Invalid grant milestones")]
+    InvalidGrantMilestones,
+    #[msg("Milestone is already approved")]
+    MilestoneAlreadyApproved,
+    #[msg("Invalid spending limit")]
+    InvalidSpendingLimit,
+    #[msg("Exceeds the spending limit")]
+    ExceedsTheSpendingLimit,
+    #[msg("Timelock is not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Invalid reward epoch")]
+    InvalidRewardEpoch,
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Invalid emission schedule")]
+    InvalidEmissionSchedule,
+    #[msg("Invalid reward pool")]
+    InvalidRewardPool,
+    #[msg("Reward pool is disabled")]
+    RewardPoolIsDisabled,
+    #[msg("Invalid withdrawal amount")]
+    InvalidWithdrawalAmount,
+    #[msg("Vesting is not revoked")]
+    VestingIsNotRevoked,
+    #[msg("Vested tokens are not claimed")]
+    VestedTokensNotClaimed,
+    #[msg("Pool is already created")]
+    PoolAlreadyCreated,
+    #[msg("Liquidity of this currency is bootstrapped by the presale")]
+    LiquidityOwnedByPresale,
+    #[msg("Summary account already migrated")]
+    SummaryAccountAlreadyMigrated,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -78,7 +158,7 @@ pub enum SwapDirection {
     Output,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 pub enum VaultAccount {
     Eda,
     Team,
@@ -87,6 +167,7 @@ pub enum VaultAccount {
     Treasury,
     Liquidity,
     Platform,
+    PresaleEscrow,
 }
 
 impl VaultAccount {
@@ -99,8 +180,21 @@ impl VaultAccount {
             VaultAccount::Treasury => "treasury",
             VaultAccount::Liquidity => "liquidity",
             VaultAccount::Platform => "platform",
+            VaultAccount::PresaleEscrow => "presale_escrow",
         }
     }
+
+    // vaults the owner can move funds from, always through a spending limit
+    pub fn is_spending_limited(&self) -> bool {
+        matches!(self, VaultAccount::Eda | VaultAccount::Treasury)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum EmissionCurve {
+    Halving,
+    LinearDecay,
+    Steps,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::enums::Currency;
+use crate::enums::{Currency, VaultAccount};
 
 #[event]
 pub struct BuyPremiumEvent {
@@ -13,12 +13,65 @@ pub struct BuyPremiumEvent {
 pub struct BuyPresaleTokenEvent {
     pub receiver: Pubkey,
     pub amount: u64,
+    pub round: u8,
+}
+
+#[event]
+pub struct TransferPresalePositionEvent {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub presale_type: u8,
+    pub amount: u64,
+    pub claimed_amount: u64,
+}
+
+#[event]
+pub struct TokenizePresalePositionEvent {
+    pub receiver: Pubkey,
+    pub presale_type: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ReferralPurchaseEvent {
+    pub referrer: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub usdc_amount: u64,
+    pub bonus: u64,
+    pub token_volume: u64,
+    pub usdc_volume: u64,
+    pub earned_amount: u64,
+}
+
+#[event]
+pub struct ClaimReferralEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RefundPresaleEvent {
+    pub receiver: Pubkey,
+    pub sol_amount: u64,
+    pub usdc_amount: u64,
 }
 
 #[event]
 pub struct ClaimRewardEvent {
     pub receiver: Pubkey,
     pub amount: u64,
+    pub pool: u8,
+    pub epoch: u32,
+}
+
+#[event]
+pub struct PublishRewardEpochEvent {
+    pub pool: u8,
+    pub epoch: u32,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub leaf_count: u32,
 }
 
 #[event]
@@ -26,3 +79,70 @@ pub struct ElwBurnEvent {
     pub process: String,
     pub amount: u64,
 }
+
+#[event]
+pub struct CreateVestingEvent {
+    pub beneficiary: Pubkey,
+    pub id: u64,
+    pub vault: VaultAccount,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct ApproveVestingMilestoneEvent {
+    pub beneficiary: Pubkey,
+    pub id: u64,
+    pub index: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimVestingEvent {
+    pub beneficiary: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RevokeVestingEvent {
+    pub beneficiary: Pubkey,
+    pub id: u64,
+    pub returned_amount: u64,
+}
+
+#[event]
+pub struct QueueWithdrawalEvent {
+    pub spending_limit: Pubkey,
+    pub id: u64,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct ExecuteWithdrawalEvent {
+    pub spending_limit: Pubkey,
+    pub id: u64,
+    pub receiver: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CancelWithdrawalEvent {
+    pub spending_limit: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TransferAdminOwnershipEvent {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct AcceptAdminOwnershipEvent {
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+}
@@ -3,9 +3,10 @@ use anchor_lang::solana_program::*;
 use anchor_spl::{
     associated_token::{self, get_associated_token_address},
     token::{self, spl_token::instruction::AuthorityType, Token},
-    token_interface::{Mint, TokenAccount},
+    token_interface::{self, Mint, TokenAccount, TransferChecked},
 };
 use chrono::{Datelike, Months, TimeZone, Utc};
+use pyth_solana_receiver_sdk::{error::GetPriceError, price_update::PriceUpdateV2};
 
 use crate::constants::*;
 use crate::enums::VaultAccount;
@@ -170,6 +171,20 @@ pub fn transfer_sol_with_pda_key<'info>(
     Ok(())
 }
 
+// closes a program owned account given as unchecked, like the anchor close constraint
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    receiver_account: &AccountInfo<'info>,
+) -> Result<()> {
+    **receiver_account.try_borrow_mut_lamports()? += account.lamports();
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&system_program::ID);
+    account.realloc(0, false)?;
+
+    Ok(())
+}
+
 pub fn wrap_sol<'info>(
     token_program: &AccountInfo<'info>,
     wsol_ata: &AccountInfo<'info>,
@@ -206,6 +221,59 @@ pub fn transfer_token_with_pda_key<'info>(
     );
 }
 
+// payment mints can be Token-2022 mints, checked transfers work with both token programs
+pub fn transfer_token_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    sender_account: &AccountInfo<'info>,
+    mint_account: &AccountInfo<'info>,
+    receiver_account: &AccountInfo<'info>,
+    authority_account: &AccountInfo<'info>,
+    transfer_amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    return token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: sender_account.clone(),
+                mint: mint_account.clone(),
+                to: receiver_account.clone(),
+                authority: authority_account.clone(),
+            },
+        ),
+        transfer_amount,
+        decimals,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_token_checked_with_pda_key<'info>(
+    key: &str,
+    bump: u8,
+    token_program: &AccountInfo<'info>,
+    sender_account: &AccountInfo<'info>,
+    mint_account: &AccountInfo<'info>,
+    receiver_account: &AccountInfo<'info>,
+    authority_account: &AccountInfo<'info>,
+    transfer_amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    return token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: sender_account.clone(),
+                mint: mint_account.clone(),
+                to: receiver_account.clone(),
+                authority: authority_account.clone(),
+            },
+            &[&[key.as_ref(), &[bump]]],
+        ),
+        transfer_amount,
+        decimals,
+    );
+}
+
 pub fn transfer_token_with_signer<'info>(
     signer_seeds: &[&[u8]],
     token_program: &AccountInfo<'info>,
@@ -301,51 +369,112 @@ pub fn get_months_difference(from: i64, to: i64) -> i32 {
     (to_date.year() - from_date.year()) * 12 + (to_date.month() as i32 - from_date.month() as i32)
 }
 
-pub fn calculate_reward_distribution(timestamp: i64) -> u64 {
-    let months = get_months_difference(PRESALE_RULES.end_time, timestamp);
-    let halving = (months / 4) as u32;
-    BASE_REWARD >> halving
-}
+// Returns a conservative (price + conf) quote and its exponent, rejecting stale or uncertain prices
+// price, confidence and exponent, rejected when stale or too uncertain
+pub fn get_oracle_price_with_confidence(
+    price_update: &PriceUpdateV2,
+    feed_id: &[u8; 32],
+    maximum_age: u64,
+    max_confidence_percentage: u16,
+) -> Result<(i64, i64, i32)> {
+    let price_data = price_update
+        .get_price_no_older_than(&Clock::get()?, maximum_age, feed_id)
+        .map_err(|error| match error {
+            GetPriceError::PriceTooOld => CustomError::StalePrice.into(),
+            error => anchor_lang::error::Error::from(error),
+        })?;
+
+    require!(price_data.price > 0, CustomError::InvalidPrice);
+
+    require!(
+        (price_data.conf as u128) * 10000
+            <= (price_data.price as u128) * (max_confidence_percentage as u128),
+        CustomError::PriceConfidenceTooWide
+    );
 
-pub fn calculate_by_percentage(total: u64, percentage: u16) -> u64 {
-    (total as f64 * percentage as f64 / 10000.0) as u64
+    Ok((
+        price_data.price,
+        price_data.conf as i64,
+        price_data.exponent,
+    ))
+}
+
+pub fn get_oracle_price(
+    price_update: &PriceUpdateV2,
+    feed_id: &[u8; 32],
+    maximum_age: u64,
+    max_confidence_percentage: u16,
+) -> Result<(i64, i32)> {
+    let (price, confidence, exponent) = get_oracle_price_with_confidence(
+        price_update,
+        feed_id,
+        maximum_age,
+        max_confidence_percentage,
+    )?;
+    let price = price + confidence; // see: https://docs.pyth.network/price-feeds/use-real-time-data/solana
+    Ok((price, exponent))
 }
 
 pub fn usdc_to_sol(amount: u64, price: i64, exponent: i32) -> u64 {
-    const SOL_DECIMALS: i32 = 9;
+    const SOL_DECIMALS: u8 = 9;
+
+    usdc_to_token(amount, price, exponent, SOL_DECIMALS)
+}
+
+pub fn usdc_to_token(amount: u64, price: i64, exponent: i32, token_decimals: u8) -> u64 {
     const USDC_DECIMALS: i32 = 6;
 
     let usdc = amount as u128;
-    let sol = price as i128;
+    let token = price as i128;
 
-    let exponent_adjust = (SOL_DECIMALS - USDC_DECIMALS) - exponent;
-    let sol_u128 = if exponent_adjust >= 0 {
+    let exponent_adjust = (token_decimals as i32 - USDC_DECIMALS) - exponent;
+    let token_u128 = if exponent_adjust >= 0 {
         let multiplier: u128 = 10u128.pow(exponent_adjust as u32);
         usdc.checked_mul(multiplier)
             .unwrap()
-            .checked_div(sol.abs() as u128)
+            .checked_div(token.abs() as u128)
             .unwrap()
     } else {
         let multiplier: u128 = 10u128.pow((-exponent_adjust) as u32);
-        usdc.checked_div((sol.abs() as u128).checked_mul(multiplier).unwrap())
+        usdc.checked_div((token.abs() as u128).checked_mul(multiplier).unwrap())
             .unwrap()
     };
 
-    sol_u128 as u64
+    token_u128 as u64
 }
 
-pub fn is_team_member(key: &Pubkey) -> bool {
-    TEAM_WALLETS.iter().any(|(wallet, _)| key.eq(wallet))
+pub fn token_to_usdc(amount: u64, price: i64, exponent: i32, token_decimals: u8) -> u64 {
+    const USDC_DECIMALS: i32 = 6;
+
+    let value = (amount as u128)
+        .checked_mul(price.unsigned_abs() as u128)
+        .unwrap();
+
+    let exponent_adjust = USDC_DECIMALS + exponent - token_decimals as i32;
+    let usdc_u128 = if exponent_adjust >= 0 {
+        value
+            .checked_mul(10u128.pow(exponent_adjust as u32))
+            .unwrap()
+    } else {
+        value
+            .checked_div(10u128.pow((-exponent_adjust) as u32))
+            .unwrap()
+    };
+
+    usdc_u128 as u64
 }
 
-pub fn get_member_percentage(address: &Pubkey) -> Option<u16> {
-    TEAM_WALLETS.iter().find_map(|(wallet, share)| {
-        if wallet == address {
-            Some(*share)
+// sorted pair hashing, compatible with OpenZeppelin merkle trees
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed_hash = leaf;
+    for proof_element in proof.iter() {
+        computed_hash = if computed_hash <= *proof_element {
+            keccak::hashv(&[&computed_hash, proof_element]).0
         } else {
-            None
-        }
-    })
+            keccak::hashv(&[proof_element, &computed_hash]).0
+        };
+    }
+    computed_hash == root
 }
 
 pub fn get_quote_mint(currency: Currency) -> Result<Pubkey> {
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, enums::*, events::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct InitializeAdminConfig<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == MULTISIG @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(AdminConfigAccount::INIT_SPACE),
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(ctx: Context<InitializeAdminConfig>, roles: AdminRoles) -> Result<()> {
+    let admin_config = &mut ctx.accounts.admin_config;
+    admin_config.owner = ctx.accounts.signer.key();
+    admin_config.pending_owner = None;
+    admin_config.roles = roles;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateAdminRoles<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        mut,
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+}
+
+pub fn update_roles(ctx: Context<UpdateAdminRoles>, roles: AdminRoles) -> Result<()> {
+    ctx.accounts.admin_config.roles = roles;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferAdminOwnership<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        mut,
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+}
+
+pub fn transfer_ownership(ctx: Context<TransferAdminOwnership>, new_owner: Pubkey) -> Result<()> {
+    let admin_config = &mut ctx.accounts.admin_config;
+
+    // the new owner has to accept, a wrong key can still be replaced
+    admin_config.pending_owner = Some(new_owner);
+
+    emit!(TransferAdminOwnershipEvent {
+        owner: admin_config.owner,
+        pending_owner: new_owner,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminOwnership<'info> {
+    #[account(
+        constraint = admin_config.pending_owner == Some(signer.key()) @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        mut,
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+}
+
+pub fn accept_ownership(ctx: Context<AcceptAdminOwnership>) -> Result<()> {
+    let admin_config = &mut ctx.accounts.admin_config;
+
+    let previous_owner = admin_config.owner;
+    admin_config.owner = ctx.accounts.signer.key();
+    admin_config.pending_owner = None;
+
+    emit!(AcceptAdminOwnershipEvent {
+        previous_owner,
+        owner: admin_config.owner,
+    });
+
+    Ok(())
+}
@@ -1,17 +1,28 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::SIGNER, enums::CustomError, functions::get_account_size,
-    state::AddressLookupTableAccount,
+    enums::CustomError,
+    functions::get_account_size,
+    state::{AddressLookupTableAccount, AdminConfigAccount},
 };
 
 #[derive(Accounts)]
 pub struct SaveAddressLookupTable<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.operator @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     #[account(
         init_if_needed,
         payer = signer,
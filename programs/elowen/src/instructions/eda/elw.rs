@@ -4,16 +4,25 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{constants::*, enums::*, functions::*};
+use crate::{enums::*, functions::*, state::*};
 
 #[derive(Accounts)]
 pub struct WithdrawEdaELW<'info> {
     #[account(
         mut,
-        constraint = signer.key() == MULTISIG @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     // Token mint
     pub elw_mint: Account<'info, Mint>,
 
@@ -28,6 +37,18 @@ pub struct WithdrawEdaELW<'info> {
     #[account(mut)]
     pub eda_token_ata: Account<'info, TokenAccount>,
 
+    // Vault spending limit
+    #[account(
+        mut,
+        seeds = [
+            b"spending_limit".as_ref(),
+            b"eda".as_ref(),
+            elw_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
+
     // Receiver
     pub receiver: SystemAccount<'info>,
     #[account(
@@ -52,6 +73,11 @@ pub fn withdraw(ctx: Context<WithdrawEdaELW>, amount: u64) -> Result<()> {
         CustomError::NotEnoughBalanceInVault
     );
 
+    // above the limit, a withdrawal has to be queued
+    ctx.accounts
+        .spending_limit
+        .spend(amount, Clock::get()?.unix_timestamp)?;
+
     let sender_account = &eda_token_ata.to_account_info();
     let token_program = &ctx.accounts.token_program.to_account_info();
     let authority_account = &ctx.accounts.eda_vault.to_account_info();
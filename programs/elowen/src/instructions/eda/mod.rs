@@ -1,7 +1,9 @@
 pub mod elw;
 pub mod sol;
+pub mod token;
 pub mod usdc;
 
 pub use elw::*;
 pub use sol::*;
+pub use token::*;
 pub use usdc::*;
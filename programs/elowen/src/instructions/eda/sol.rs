@@ -1,14 +1,24 @@
 use anchor_lang::prelude::*;
 
-use crate::{constants::*, enums::*, functions::*};
+use crate::{constants::*, enums::*, functions::*, state::*};
 
 #[derive(Accounts)]
 pub struct WithdrawEdaSOL<'info> {
     #[account(
         mut,
-        constraint = signer.key() == MULTISIG @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     /// CHECK: EDA vault
     #[account(
         mut,
@@ -18,6 +28,17 @@ pub struct WithdrawEdaSOL<'info> {
         bump,
     )]
     pub eda_vault: UncheckedAccount<'info>,
+    // Vault spending limit
+    #[account(
+        mut,
+        seeds = [
+            b"spending_limit".as_ref(),
+            b"eda".as_ref(),
+            WSOL_MINT.as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
     // Receiver for SOL
     #[account(mut)]
     pub receiver: SystemAccount<'info>,
@@ -29,6 +50,11 @@ pub fn withdraw(ctx: Context<WithdrawEdaSOL>, amount: u64) -> Result<()> {
 
     require!(lamports >= amount, CustomError::NotEnoughBalanceInVault);
 
+    // above the limit, a withdrawal has to be queued
+    ctx.accounts
+        .spending_limit
+        .spend(amount, Clock::get()?.unix_timestamp)?;
+
     transfer_sol_with_pda_key(
         "eda",
         ctx.bumps.eda_vault,
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{enums::*, functions::*, state::*};
+
+// Withdraws a registered payment mint, token or Token-2022
+#[derive(Accounts)]
+pub struct WithdrawEdaToken<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Payment mint and its registry entry
+    #[account(mint::token_program = token_program)]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        seeds = [
+            b"payment_mint".as_ref(),
+            payment_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_mint_account: Box<Account<'info, PaymentMintAccount>>,
+
+    /// CHECK: EDA vault
+    #[account(
+        seeds = [
+            b"eda".as_ref(),
+        ],
+        bump,
+    )]
+    pub eda_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = eda_vault,
+        associated_token::token_program = token_program
+    )]
+    pub eda_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Vault spending limit
+    #[account(
+        mut,
+        seeds = [
+            b"spending_limit".as_ref(),
+            b"eda".as_ref(),
+            payment_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
+
+    // Receiver
+    pub receiver: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub receiver_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Official programs, token or Token-2022 program of the payment mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn withdraw(ctx: Context<WithdrawEdaToken>, amount: u64) -> Result<()> {
+    let eda_token_ata = &ctx.accounts.eda_token_ata;
+
+    require!(
+        eda_token_ata.amount >= amount,
+        CustomError::NotEnoughBalanceInVault
+    );
+
+    // above the limit, a withdrawal has to be queued
+    ctx.accounts
+        .spending_limit
+        .spend(amount, Clock::get()?.unix_timestamp)?;
+
+    transfer_token_checked_with_pda_key(
+        "eda",
+        ctx.bumps.eda_vault,
+        &ctx.accounts.token_program.to_account_info(),
+        &eda_token_ata.to_account_info(),
+        &ctx.accounts.payment_mint.to_account_info(),
+        &ctx.accounts.receiver_token_ata.to_account_info(),
+        &ctx.accounts.eda_vault.to_account_info(),
+        amount,
+        ctx.accounts.payment_mint_account.decimals,
+    )?;
+
+    Ok(())
+}
@@ -9,15 +9,31 @@ use mpl_token_metadata::{
     ID as METADATA_ID,
 };
 
-use crate::{constants::*, enums::CustomError, functions::*, state::*};
+use crate::{
+    constants::*,
+    enums::CustomError,
+    functions::*,
+    math::{apply_bps, Rounding},
+    state::*,
+};
 
 #[derive(Accounts)]
 pub struct InitializeElw<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.operator @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     #[account(
         init,
         payer = signer,
@@ -214,27 +230,24 @@ pub fn initialize(ctx: Context<InitializeElw>, metadata_uri: &str) -> Result<()>
     )
     .invoke_signed(signers_seeds)?;
 
+    let [team_amount, reward_amount, presale_amount, liquidity_amount, eda_amount] =
+        get_initial_mint_split(SUPPLY);
+
     let mints = [
-        (
-            &ctx.accounts.team_token_ata.to_account_info(),
-            calculate_by_percentage(SUPPLY, TEAM_PERCENTAGE),
-        ),
+        (&ctx.accounts.team_token_ata.to_account_info(), team_amount),
         (
             &ctx.accounts.reward_token_ata.to_account_info(),
-            calculate_by_percentage(SUPPLY, REWARD_PERCENTAGE),
+            reward_amount,
         ),
         (
             &ctx.accounts.presale_token_ata.to_account_info(),
-            calculate_by_percentage(SUPPLY, PRESALE_PERCENTAGE),
+            presale_amount,
         ),
         (
             &ctx.accounts.liquidity_token_ata.to_account_info(),
-            calculate_by_percentage(SUPPLY, LIQUIDITY_PERCENTAGE),
-        ),
-        (
-            &ctx.accounts.eda_token_ata.to_account_info(),
-            calculate_by_percentage(SUPPLY, EDA_PERCENTAGE),
+            liquidity_amount,
         ),
+        (&ctx.accounts.eda_token_ata.to_account_info(), eda_amount),
     ];
 
     let mint_account = &elw_mint.to_account_info();
@@ -264,3 +277,69 @@ pub fn initialize(ctx: Context<InitializeElw>, metadata_uri: &str) -> Result<()>
 
     Ok(())
 }
+
+// team, reward, presale, liquidity and EDA amounts of the initial mint
+fn get_initial_mint_split(supply: u64) -> [u64; 5] {
+    let team_amount = apply_bps(supply, TEAM_PERCENTAGE, Rounding::Floor);
+    let reward_amount = apply_bps(supply, REWARD_PERCENTAGE, Rounding::Floor);
+    let presale_amount = apply_bps(supply, PRESALE_PERCENTAGE, Rounding::Floor);
+    let liquidity_amount = apply_bps(supply, LIQUIDITY_PERCENTAGE, Rounding::Floor);
+    // EDA takes the rest, so exactly the supply is minted
+    let eda_amount = supply - team_amount - reward_amount - presale_amount - liquidity_amount;
+
+    [
+        team_amount,
+        reward_amount,
+        presale_amount,
+        liquidity_amount,
+        eda_amount,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::BASIS_POINTS;
+
+    #[test]
+    fn allocation_percentages_are_the_whole_supply() {
+        assert_eq!(
+            TEAM_PERCENTAGE
+                + REWARD_PERCENTAGE
+                + PRESALE_PERCENTAGE
+                + LIQUIDITY_PERCENTAGE
+                + EDA_PERCENTAGE,
+            BASIS_POINTS as u16
+        );
+    }
+
+    #[test]
+    fn initial_mint_split_is_exactly_the_supply() {
+        let split = get_initial_mint_split(SUPPLY);
+
+        assert_eq!(split.iter().sum::<u64>(), SUPPLY);
+        assert_eq!(
+            split,
+            [
+                TEAM_PERCENTAGE,
+                REWARD_PERCENTAGE,
+                PRESALE_PERCENTAGE,
+                LIQUIDITY_PERCENTAGE,
+                EDA_PERCENTAGE,
+            ]
+            .map(|bps| apply_bps(SUPPLY, bps, Rounding::Floor))
+        );
+    }
+
+    #[test]
+    fn initial_mint_split_gives_the_rounding_dust_to_the_eda() {
+        for supply in [0, 1, 9_999, 10_001, SUPPLY - 1, SUPPLY + 1, u64::MAX] {
+            let split = get_initial_mint_split(supply);
+            let eda_floor = apply_bps(supply, EDA_PERCENTAGE, Rounding::Floor);
+
+            assert_eq!(split.iter().sum::<u64>(), supply);
+            // each of the four floored parts loses less than one unit
+            assert!(split[4] >= eda_floor && split[4] - eda_floor < 5);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::{enums::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct InitializeEmissionSchedule<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Emission schedule
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(EmissionScheduleAccount::INIT_SPACE),
+        seeds = [
+            b"emission_schedule".as_ref(),
+        ],
+        bump,
+    )]
+    pub emission_schedule: Account<'info, EmissionScheduleAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_schedule(
+    ctx: Context<InitializeEmissionSchedule>,
+    schedule: EmissionSchedule,
+) -> Result<()> {
+    schedule.validate()?;
+
+    ctx.accounts.emission_schedule.schedule = schedule;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateEmissionSchedule<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Emission schedule
+    #[account(
+        mut,
+        seeds = [
+            b"emission_schedule".as_ref(),
+        ],
+        bump,
+    )]
+    pub emission_schedule: Account<'info, EmissionScheduleAccount>,
+}
+
+pub fn update_schedule(
+    ctx: Context<UpdateEmissionSchedule>,
+    schedule: EmissionSchedule,
+) -> Result<()> {
+    // published reward epochs keep their amounts
+    schedule.validate()?;
+
+    ctx.accounts.emission_schedule.schedule = schedule;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetEmission<'info> {
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Account<'info, PresaleConfigAccount>,
+
+    // Emission schedule
+    #[account(
+        seeds = [
+            b"emission_schedule".as_ref(),
+        ],
+        bump,
+    )]
+    pub emission_schedule: Account<'info, EmissionScheduleAccount>,
+}
+
+pub fn get_emission(ctx: Context<GetEmission>, timestamp: i64) -> Result<u64> {
+    Ok(ctx
+        .accounts
+        .emission_schedule
+        .schedule
+        .get_emission(ctx.accounts.presale_config.rules.end_time, timestamp))
+}
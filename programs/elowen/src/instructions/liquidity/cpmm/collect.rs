@@ -20,7 +20,8 @@ use crate::{
     enums::{Currency, CustomError},
     events::ElwBurnEvent,
     functions::*,
-    state::PlatformAccount,
+    math::{apply_bps, Rounding},
+    state::{AdminConfigAccount, PlatformAccount},
 };
 
 #[derive(Accounts)]
@@ -28,10 +29,19 @@ use crate::{
 pub struct CollectLockedLiquidityFees<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.liquidity_manager @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Box<Account<'info, AdminConfigAccount>>,
+
     pub locking_program: Program<'info, RaydiumLiquidityLocking>,
 
     /// CHECK: the authority of token vault that cp is locked
@@ -222,13 +232,28 @@ pub fn collect(ctx: Context<CollectLockedLiquidityFees>, currency: Currency) ->
 
     require!(withdraw_elw_amount > 0, CustomError::NoRewardInVault);
 
-    let eda_elw_amount = calculate_by_percentage(withdraw_elw_amount, COLLECT_FEE_EDA_PERCENTAGE);
-    let eda_quote_amount =
-        calculate_by_percentage(withdraw_quote_amount, COLLECT_FEE_EDA_PERCENTAGE);
-
-    let burn_elw_amount = calculate_by_percentage(withdraw_elw_amount, COLLECT_FEE_BURN_PERCENTAGE);
-    let buyback_quote_amount =
-        calculate_by_percentage(withdraw_quote_amount, COLLECT_FEE_BURN_PERCENTAGE);
+    // shares round down, the rounding dust stays in the liquidity vault
+    let eda_elw_amount = apply_bps(
+        withdraw_elw_amount,
+        COLLECT_FEE_EDA_PERCENTAGE,
+        Rounding::Floor,
+    );
+    let eda_quote_amount = apply_bps(
+        withdraw_quote_amount,
+        COLLECT_FEE_EDA_PERCENTAGE,
+        Rounding::Floor,
+    );
+
+    let burn_elw_amount = apply_bps(
+        withdraw_elw_amount,
+        COLLECT_FEE_BURN_PERCENTAGE,
+        Rounding::Floor,
+    );
+    let buyback_quote_amount = apply_bps(
+        withdraw_quote_amount,
+        COLLECT_FEE_BURN_PERCENTAGE,
+        Rounding::Floor,
+    );
 
     cp_swap_cpi::swap_base_input(
         CpiContext::new_with_signer(
@@ -17,7 +17,7 @@ use crate::{
     constants::*,
     enums::{Currency, CustomError},
     functions::*,
-    state::{LockedLpStateAccount, LpStateAccount, PlatformAccount},
+    state::{AdminConfigAccount, LockedLpStateAccount, LpStateAccount, PlatformAccount},
 };
 
 #[derive(Accounts)]
@@ -25,10 +25,19 @@ use crate::{
 pub struct LiquidityDeposit<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.liquidity_manager @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Box<Account<'info, AdminConfigAccount>>,
+
     // Raydium CPMM program
     #[account(
         address = raydium_cp_swap::ID,
@@ -23,7 +23,10 @@ use crate::{
     constants::*,
     enums::{Currency, CustomError},
     functions::*,
-    state::{LockedLpStateAccount, LpStateAccount, PlatformAccount},
+    state::{
+        AdminConfigAccount, LockedLpStateAccount, LpStateAccount, PlatformAccount,
+        PresaleConfigAccount, SummaryAccount,
+    },
 };
 
 #[derive(Accounts)]
@@ -31,10 +34,37 @@ use crate::{
 pub struct LiquidityInitialize<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.liquidity_manager @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Box<Account<'info, AdminConfigAccount>>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
     // Raydium CPMM program
     #[account(
         address = raydium_cp_swap::ID,
@@ -168,7 +198,7 @@ pub struct LiquidityInitialize<'info> {
     pub liquidity_lp_token_ata: UncheckedAccount<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = signer,
         space = get_account_size(LpStateAccount::INIT_SPACE),
         seeds = [
@@ -253,6 +283,22 @@ pub fn initialize(
     quote_amount: u64,
     open_time: u64,
 ) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &ctx.accounts.presale_summary_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    // finalize_presale owns the pools of every currency a successful sale raised
+    let raised_amount = if currency == Currency::SOL {
+        summary_account.sol_raised
+    } else {
+        summary_account.usdc_raised
+    };
+    require!(
+        !rules.is_soft_cap_reached(summary_account.token_sold) || raised_amount == 0,
+        CustomError::LiquidityOwnedByPresale
+    );
+
     // Combine validations
     require!(
         ctx.accounts.liquidity_elw_token_ata.amount >= elw_amount
@@ -260,9 +306,99 @@ pub fn initialize(
         CustomError::InsufficientLiquidity
     );
 
+    create_locked_pool(
+        &LockedPoolAccounts {
+            payer: &ctx.accounts.signer.to_account_info(),
+            liquidity_vault: &ctx.accounts.liquidity_vault.to_account_info(),
+            liquidity_vault_bump: ctx.bumps.liquidity_vault,
+            amm_config: &ctx.accounts.amm_config,
+            cp_swap_program: &ctx.accounts.cp_swap_program.to_account_info(),
+            cp_swap_authority: &ctx.accounts.cp_swap_authority.to_account_info(),
+            create_pool_fee: &ctx.accounts.create_pool_fee.to_account_info(),
+            pool_state: &ctx.accounts.pool_state.to_account_info(),
+            observation_state: &ctx.accounts.observation_state.to_account_info(),
+            elw_mint: &ctx.accounts.elw_mint.to_account_info(),
+            quote_mint: &ctx.accounts.quote_mint.to_account_info(),
+            elw_vault: &ctx.accounts.elw_vault.to_account_info(),
+            quote_vault: &ctx.accounts.quote_vault.to_account_info(),
+            lp_mint: &ctx.accounts.lp_mint.to_account_info(),
+            liquidity_elw_token_ata: &ctx.accounts.liquidity_elw_token_ata.to_account_info(),
+            liquidity_quote_token_ata: &ctx.accounts.liquidity_quote_token_ata.to_account_info(),
+            liquidity_lp_token_ata: &ctx.accounts.liquidity_lp_token_ata.to_account_info(),
+            fee_nft_mint: &ctx.accounts.fee_nft_mint.to_account_info(),
+            fee_nft_account: &ctx.accounts.fee_nft_account.to_account_info(),
+            locking_program: &ctx.accounts.locking_program.to_account_info(),
+            locking_authority: &ctx.accounts.locking_authority.to_account_info(),
+            locked_liquidity: &ctx.accounts.locked_liquidity.to_account_info(),
+            locked_lp_token_ata: &ctx.accounts.locked_lp_token_ata.to_account_info(),
+            metadata_account: &ctx.accounts.metadata_account,
+            metadata_program: &ctx.accounts.metadata_program,
+            rent: &ctx.accounts.rent.to_account_info(),
+            token_program: &ctx.accounts.token_program.to_account_info(),
+            system_program: &ctx.accounts.system_program.to_account_info(),
+            associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+        },
+        &mut ctx.accounts.lp_state,
+        &mut ctx.accounts.locked_lp_state,
+        currency,
+        elw_amount,
+        quote_amount,
+        open_time,
+    )
+}
+
+// Accounts to create a CPMM pool from the liquidity vault and lock its LP tokens
+pub struct LockedPoolAccounts<'a, 'info> {
+    pub payer: &'a AccountInfo<'info>,
+    pub liquidity_vault: &'a AccountInfo<'info>,
+    pub liquidity_vault_bump: u8,
+    pub amm_config: &'a Account<'info, AmmConfig>,
+    pub cp_swap_program: &'a AccountInfo<'info>,
+    pub cp_swap_authority: &'a AccountInfo<'info>,
+    pub create_pool_fee: &'a AccountInfo<'info>,
+    pub pool_state: &'a AccountInfo<'info>,
+    pub observation_state: &'a AccountInfo<'info>,
+    pub elw_mint: &'a AccountInfo<'info>,
+    pub quote_mint: &'a AccountInfo<'info>,
+    pub elw_vault: &'a AccountInfo<'info>,
+    pub quote_vault: &'a AccountInfo<'info>,
+    pub lp_mint: &'a AccountInfo<'info>,
+    pub liquidity_elw_token_ata: &'a AccountInfo<'info>,
+    pub liquidity_quote_token_ata: &'a AccountInfo<'info>,
+    pub liquidity_lp_token_ata: &'a AccountInfo<'info>,
+    pub fee_nft_mint: &'a AccountInfo<'info>,
+    pub fee_nft_account: &'a AccountInfo<'info>,
+    pub locking_program: &'a AccountInfo<'info>,
+    pub locking_authority: &'a AccountInfo<'info>,
+    pub locked_liquidity: &'a AccountInfo<'info>,
+    pub locked_lp_token_ata: &'a AccountInfo<'info>,
+    pub metadata_account: &'a AccountInfo<'info>,
+    pub metadata_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+}
+
+// creates the pool with the vault as creator and locks all of its LP tokens,
+// the payer only funds the rent and gets back what the pool did not use
+pub fn create_locked_pool(
+    accounts: &LockedPoolAccounts,
+    lp_state: &mut LpStateAccount,
+    locked_lp_state: &mut LockedLpStateAccount,
+    currency: Currency,
+    elw_amount: u64,
+    quote_amount: u64,
+    open_time: u64,
+) -> Result<()> {
+    require!(
+        lp_state.pool_state == Pubkey::default(),
+        CustomError::PoolAlreadyCreated
+    );
+
     // Calculate needed lamports in one step
     let needed_lamports = 7_960_720 // needed
-        + ctx.accounts.amm_config.create_pool_fee
+        + accounts.amm_config.create_pool_fee
         + Rent::get()?.minimum_balance(82) * 2 // mint accounts
         + Rent::get()?.minimum_balance(165) * 5 // token accounts
         + Rent::get()?.minimum_balance(PoolState::LEN) // pool state
@@ -270,38 +406,36 @@ pub fn initialize(
         + Rent::get()?.minimum_balance(ObservationState::LEN) // observation state
         + Rent::get()?.minimum_balance(LockedCpLiquidityState::LEN); // locked liquidity state
 
-    transfer_sol(
-        &ctx.accounts.signer,
-        &ctx.accounts.liquidity_vault,
-        needed_lamports,
-    )?;
+    let vault_lamports = accounts.liquidity_vault.lamports();
+
+    transfer_sol(accounts.payer, accounts.liquidity_vault, needed_lamports)?;
 
-    let signer_seeds: &[&[&[u8]]] = &[&[b"liquidity".as_ref(), &[ctx.bumps.liquidity_vault]]];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"liquidity".as_ref(), &[accounts.liquidity_vault_bump]]];
 
     cp_swap_cpi::initialize(
         CpiContext::new_with_signer(
-            ctx.accounts.cp_swap_program.to_account_info(),
+            accounts.cp_swap_program.clone(),
             cp_swap_cpi::accounts::Initialize {
-                creator: ctx.accounts.liquidity_vault.to_account_info(),
-                amm_config: ctx.accounts.amm_config.to_account_info(),
-                authority: ctx.accounts.cp_swap_authority.to_account_info(),
-                pool_state: ctx.accounts.pool_state.to_account_info(),
-                token_0_mint: ctx.accounts.elw_mint.to_account_info(),
-                token_1_mint: ctx.accounts.quote_mint.to_account_info(),
-                lp_mint: ctx.accounts.lp_mint.to_account_info(),
-                creator_token_0: ctx.accounts.liquidity_elw_token_ata.to_account_info(),
-                creator_token_1: ctx.accounts.liquidity_quote_token_ata.to_account_info(),
-                creator_lp_token: ctx.accounts.liquidity_lp_token_ata.to_account_info(),
-                token_0_vault: ctx.accounts.elw_vault.to_account_info(),
-                token_1_vault: ctx.accounts.quote_vault.to_account_info(),
-                create_pool_fee: ctx.accounts.create_pool_fee.to_account_info(),
-                observation_state: ctx.accounts.observation_state.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                token_0_program: ctx.accounts.token_program.to_account_info(),
-                token_1_program: ctx.accounts.token_program.to_account_info(),
-                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
+                creator: accounts.liquidity_vault.clone(),
+                amm_config: accounts.amm_config.to_account_info(),
+                authority: accounts.cp_swap_authority.clone(),
+                pool_state: accounts.pool_state.clone(),
+                token_0_mint: accounts.elw_mint.clone(),
+                token_1_mint: accounts.quote_mint.clone(),
+                lp_mint: accounts.lp_mint.clone(),
+                creator_token_0: accounts.liquidity_elw_token_ata.clone(),
+                creator_token_1: accounts.liquidity_quote_token_ata.clone(),
+                creator_lp_token: accounts.liquidity_lp_token_ata.clone(),
+                token_0_vault: accounts.elw_vault.clone(),
+                token_1_vault: accounts.quote_vault.clone(),
+                create_pool_fee: accounts.create_pool_fee.clone(),
+                observation_state: accounts.observation_state.clone(),
+                token_program: accounts.token_program.clone(),
+                token_0_program: accounts.token_program.clone(),
+                token_1_program: accounts.token_program.clone(),
+                associated_token_program: accounts.associated_token_program.clone(),
+                system_program: accounts.system_program.clone(),
+                rent: accounts.rent.clone(),
             },
             signer_seeds,
         ),
@@ -310,40 +444,40 @@ pub fn initialize(
         open_time,
     )?;
 
-    let creator_lp_token = reload_unchecked_token_account(&ctx.accounts.liquidity_lp_token_ata)?;
+    let creator_lp_token = reload_token_account_by_info(accounts.liquidity_lp_token_ata)?;
 
     create_token_account(
-        &ctx.accounts.signer,
-        &ctx.accounts.token_program,
-        &ctx.accounts.system_program,
-        &ctx.accounts.lp_mint,
-        &ctx.accounts.locked_lp_token_ata,
-        &ctx.accounts.locking_authority,
+        accounts.payer,
+        accounts.token_program,
+        accounts.system_program,
+        accounts.lp_mint,
+        accounts.locked_lp_token_ata,
+        accounts.locking_authority,
     )?;
 
     locking_cpi::lock_cp_liquidity(
         CpiContext::new_with_signer(
-            ctx.accounts.locking_program.to_account_info(),
+            accounts.locking_program.clone(),
             locking_cpi::accounts::LockCpLiquidity {
-                authority: ctx.accounts.locking_authority.to_account_info(),
-                payer: ctx.accounts.liquidity_vault.to_account_info(),
-                liquidity_owner: ctx.accounts.liquidity_vault.to_account_info(),
-                fee_nft_owner: ctx.accounts.liquidity_vault.to_account_info(),
-                fee_nft_mint: ctx.accounts.fee_nft_mint.to_account_info(),
-                fee_nft_account: ctx.accounts.fee_nft_account.to_account_info(),
-                pool_state: ctx.accounts.pool_state.to_account_info(),
-                locked_liquidity: ctx.accounts.locked_liquidity.to_account_info(),
-                lp_mint: ctx.accounts.lp_mint.to_account_info(),
-                liquidity_owner_lp: ctx.accounts.liquidity_lp_token_ata.to_account_info(),
-                locked_lp_vault: ctx.accounts.locked_lp_token_ata.to_account_info(),
-                token_0_vault: ctx.accounts.elw_vault.to_account_info(),
-                token_1_vault: ctx.accounts.quote_vault.to_account_info(),
-                metadata_account: ctx.accounts.metadata_account.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                token_program: ctx.accounts.token_program.to_account_info(),
-                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-                metadata_program: ctx.accounts.metadata_program.to_account_info(),
+                authority: accounts.locking_authority.clone(),
+                payer: accounts.liquidity_vault.clone(),
+                liquidity_owner: accounts.liquidity_vault.clone(),
+                fee_nft_owner: accounts.liquidity_vault.clone(),
+                fee_nft_mint: accounts.fee_nft_mint.clone(),
+                fee_nft_account: accounts.fee_nft_account.clone(),
+                pool_state: accounts.pool_state.clone(),
+                locked_liquidity: accounts.locked_liquidity.clone(),
+                lp_mint: accounts.lp_mint.clone(),
+                liquidity_owner_lp: accounts.liquidity_lp_token_ata.clone(),
+                locked_lp_vault: accounts.locked_lp_token_ata.clone(),
+                token_0_vault: accounts.elw_vault.clone(),
+                token_1_vault: accounts.quote_vault.clone(),
+                metadata_account: accounts.metadata_account.clone(),
+                rent: accounts.rent.clone(),
+                system_program: accounts.system_program.clone(),
+                token_program: accounts.token_program.clone(),
+                associated_token_program: accounts.associated_token_program.clone(),
+                metadata_program: accounts.metadata_program.clone(),
             },
             signer_seeds,
         ),
@@ -351,26 +485,30 @@ pub fn initialize(
         true,
     )?;
 
-    // just for case
-    if ctx.accounts.liquidity_vault.lamports() > 0 {
+    // only the unused top-up goes back, other vault lamports stay
+    let unused_lamports = accounts
+        .liquidity_vault
+        .lamports()
+        .saturating_sub(vault_lamports);
+    if unused_lamports > 0 {
         transfer_sol_with_pda_key(
             "liquidity",
-            ctx.bumps.liquidity_vault,
-            &ctx.accounts.liquidity_vault,
-            &ctx.accounts.signer,
-            ctx.accounts.liquidity_vault.lamports(),
+            accounts.liquidity_vault_bump,
+            accounts.liquidity_vault,
+            accounts.payer,
+            unused_lamports,
         )?;
     }
 
     // lp state
-    ctx.accounts.lp_state.elw_amount = elw_amount;
-    ctx.accounts.lp_state.quote_amount = quote_amount;
-    ctx.accounts.lp_state.quote_currency = currency as u8;
-    ctx.accounts.lp_state.lp_amount = creator_lp_token.amount;
-    ctx.accounts.lp_state.pool_state = ctx.accounts.pool_state.key();
+    lp_state.elw_amount = elw_amount;
+    lp_state.quote_amount = quote_amount;
+    lp_state.quote_currency = currency as u8;
+    lp_state.lp_amount = creator_lp_token.amount;
+    lp_state.pool_state = accounts.pool_state.key();
     // locked lp state
-    ctx.accounts.locked_lp_state.quote_currency = currency as u8;
-    ctx.accounts.locked_lp_state.locked_lp_amount = creator_lp_token.amount;
+    locked_lp_state.quote_currency = currency as u8;
+    locked_lp_state.locked_lp_amount = creator_lp_token.amount;
 
     Ok(())
 }
@@ -11,9 +11,10 @@ use raydium_cp_swap::{
 };
 
 use crate::{
-    constants::{SIGNER, WSOL_MINT},
+    constants::WSOL_MINT,
     enums::{Currency, CustomError, SwapDirection, VaultAccount},
     functions::*,
+    state::AdminConfigAccount,
 };
 
 #[derive(Accounts)]
@@ -116,10 +117,19 @@ pub struct LiquiditySwap<'info> {
 pub struct LiquidityVaultSwap<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.liquidity_manager @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Box<Account<'info, AdminConfigAccount>>,
+
     /// CHECK: vault is a vault account
     #[account(mut)]
     pub vault: UncheckedAccount<'info>,
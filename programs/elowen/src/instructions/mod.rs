@@ -1,9 +1,13 @@
+pub mod admin;
 pub mod alt;
 pub mod eda;
 pub mod elw;
+pub mod emission;
 pub mod liquidity;
 pub mod platform;
 pub mod premium;
 pub mod presale;
 pub mod reward;
 pub mod team;
+pub mod vesting;
+pub mod withdrawal;
@@ -4,16 +4,25 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{constants::*, enums::*, events::ElwBurnEvent, functions::*, state::*};
+use crate::{enums::*, events::ElwBurnEvent, functions::*, state::*};
 
 #[derive(Accounts)]
 pub struct WithdrawPlatformELW<'info> {
     #[account(
         mut,
-        constraint = signer.key() == MULTISIG @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     // Platform PDA
     #[account(
         seeds = [
@@ -120,10 +129,19 @@ pub fn withdraw(ctx: Context<WithdrawPlatformELW>, amount: u64) -> Result<()> {
 pub struct BurnPlatformELW<'info> {
     #[account(
         mut,
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.operator @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     // Platform PDA
     #[account(
         seeds = [
@@ -9,6 +9,8 @@ use crate::{
     enums::*,
     events::{BuyPremiumEvent, ElwBurnEvent},
     functions::*,
+    math::{split_bps, Rounding},
+    state::AdminConfigAccount,
 };
 
 #[derive(Accounts)]
@@ -16,9 +18,19 @@ pub struct BuyPremium<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>, // user account
     #[account(
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.operator @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     // ELW mint
     #[account(mut)]
     pub elw_mint: Account<'info, Mint>,
@@ -99,7 +111,8 @@ pub fn buy(ctx: Context<BuyPremium>, amount_to_pay: u64, currency: Currency) ->
             CustomError::InsufficientBalance,
         );
 
-        let burn_amount = calculate_by_percentage(amount_to_pay, PREMIUM_ELW_BURN_PERCENTAGE);
+        let (burn_amount, treasury_amount) =
+            split_bps(amount_to_pay, PREMIUM_ELW_BURN_PERCENTAGE, Rounding::Floor);
 
         burn_token(
             &token_program.to_account_info(),
@@ -119,7 +132,7 @@ pub fn buy(ctx: Context<BuyPremium>, amount_to_pay: u64, currency: Currency) ->
             &buyer_token_ata.to_account_info(),
             &treasury_token_ata.to_account_info(),
             &buyer.to_account_info(),
-            amount_to_pay - burn_amount,
+            treasury_amount,
         )?;
     } else {
         require!(false, CustomError::InvalidCurrency);
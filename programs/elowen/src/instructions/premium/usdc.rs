@@ -4,16 +4,25 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{constants::*, enums::*, functions::*};
+use crate::{constants::*, enums::*, functions::*, state::*};
 
 #[derive(Accounts)]
 pub struct WithdrawTreasuryUSDC<'info> {
     #[account(
         mut,
-        constraint = signer.key() == MULTISIG @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     // Token mint
     #[account(address = USDC_MINT)]
     pub usdc_mint: Account<'info, Mint>,
@@ -34,6 +43,18 @@ pub struct WithdrawTreasuryUSDC<'info> {
     )]
     pub treasury_usdc_ata: Account<'info, TokenAccount>,
 
+    // Vault spending limit
+    #[account(
+        mut,
+        seeds = [
+            b"spending_limit".as_ref(),
+            b"treasury".as_ref(),
+            usdc_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
+
     // Receiver
     pub receiver: SystemAccount<'info>,
     #[account(
@@ -58,6 +79,11 @@ pub fn withdraw(ctx: Context<WithdrawTreasuryUSDC>, amount: u64) -> Result<()> {
         CustomError::NotEnoughBalanceInVault
     );
 
+    // above the limit, a withdrawal has to be queued
+    ctx.accounts
+        .spending_limit
+        .spend(amount, Clock::get()?.unix_timestamp)?;
+
     let sender_account = &treasury_usdc_ata.to_account_info();
     let token_program = &ctx.accounts.token_program.to_account_info();
     let authority_account = &ctx.accounts.treasury_vault.to_account_info();
@@ -1,15 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::{constants::*, enums::*, events::ElwBurnEvent, functions::*, state::*};
+use crate::{enums::*, events::ElwBurnEvent, functions::*, state::*};
 
 #[derive(Accounts)]
 pub struct BurnUnsoldElw<'info> {
     #[account(
-        constraint = signer.key() == SIGNER @ CustomError::Unauthorized,
+        constraint = signer.key() == admin_config.roles.operator @ CustomError::Unauthorized,
     )]
     pub signer: Signer<'info>,
 
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
     // Token mint
     #[account(mut)]
     pub elw_mint: Account<'info, Mint>,
@@ -26,6 +35,15 @@ pub struct BurnUnsoldElw<'info> {
     #[account(mut)]
     pub presale_token_ata: Account<'info, TokenAccount>,
 
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Account<'info, PresaleConfigAccount>,
+
     // Total sales account
     #[account(
         mut,
@@ -41,9 +59,10 @@ pub struct BurnUnsoldElw<'info> {
 }
 
 pub fn burn(ctx: Context<BurnUnsoldElw>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
     let summary_account = &mut ctx.accounts.presale_summary_account;
 
-    let burn_amount = summary_account.total_amount - summary_account.token_sold;
+    let burn_amount = summary_account.get_unsold_amount(rules);
 
     require!(burn_amount > 0, CustomError::AllTokensSold);
 
@@ -53,7 +72,7 @@ pub fn burn(ctx: Context<BurnUnsoldElw>) -> Result<()> {
     );
 
     require!(
-        PRESALE_RULES.is_presale_ended(),
+        rules.is_presale_ended(),
         CustomError::CannotBurnUntilPresaleDone
     );
 
@@ -2,10 +2,18 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
 };
 use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
 
-use crate::{constants::*, enums::*, events::BuyPresaleTokenEvent, functions::*, state::*};
+use crate::{
+    constants::*,
+    enums::*,
+    events::{BuyPresaleTokenEvent, ReferralPurchaseEvent},
+    functions::*,
+    math::{apply_bps, Rounding},
+    state::*,
+};
 
 #[derive(Accounts)]
 #[instruction(presale_type: PresaleType)]
@@ -28,9 +36,6 @@ pub struct BuyPresaleElw<'info> {
     // USDC mint
     #[account(address = USDC_MINT)]
     pub usdc_mint: Account<'info, Mint>,
-    // WSOl mint
-    #[account(address = WSOL_MINT)]
-    pub wsol_mint: Account<'info, Mint>,
 
     // Receiver presale account
     #[account(
@@ -46,69 +51,81 @@ pub struct BuyPresaleElw<'info> {
     )]
     pub receiver_purchase_account: Account<'info, PurchaseAccount>,
 
-    // Total sales account
+    // Receiver allowlist account, only needed before the public phase
     #[account(
         init_if_needed,
         payer = receiver,
-        space = get_account_size(SummaryAccount::INIT_SPACE),
+        space = get_account_size(AllowlistAccount::INIT_SPACE),
         seeds = [
-            b"presale_summary".as_ref(),
+            b"allowlist".as_ref(),
+            receiver.key().as_ref(),
         ],
         bump,
     )]
-    pub presale_summary_account: Account<'info, SummaryAccount>,
+    pub receiver_allowlist_account: Option<Box<Account<'info, AllowlistAccount>>>,
 
-    /// CHECK: Presale vault
+    // Referrer account, only for referred purchases
     #[account(
+        mut,
         seeds = [
-            b"presale".as_ref(),
+            b"referral".as_ref(),
+            referral_account.referrer.as_ref(),
         ],
         bump,
     )]
-    pub presale_vault: UncheckedAccount<'info>,
+    pub referral_account: Option<Box<Account<'info, ReferralAccount>>>,
+
+    // Presale config
     #[account(
         mut,
-        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Presale),
-    )]
-    pub presale_token_ata: Account<'info, TokenAccount>,
-    /// CHECK: Liquidity vault
-    #[account(
         seeds = [
-            b"liquidity".as_ref(),
+            b"presale_config".as_ref(),
         ],
         bump,
     )]
-    pub liquidity_vault: UncheckedAccount<'info>,
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
     #[account(
         init_if_needed,
         payer = receiver,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = liquidity_vault
+        space = get_account_size(SummaryAccount::INIT_SPACE),
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
     )]
-    pub liquidity_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub presale_summary_account: Account<'info, SummaryAccount>,
+
+    /// CHECK: Presale vault
     #[account(
-        init_if_needed,
-        payer = receiver,
-        associated_token::mint = wsol_mint,
-        associated_token::authority = liquidity_vault
+        seeds = [
+            b"presale".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Presale),
     )]
-    pub liquidity_wsol_ata: Box<Account<'info, TokenAccount>>,
-    /// CHECK: EDA vault
+    pub presale_token_ata: Account<'info, TokenAccount>,
+    /// CHECK: Presale escrow vault, holds payments until the presale ends
     #[account(
         mut,
         seeds = [
-            b"eda".as_ref(),
+            b"presale_escrow".as_ref(),
         ],
         bump,
     )]
-    pub eda_vault: UncheckedAccount<'info>,
+    pub presale_escrow: UncheckedAccount<'info>,
     #[account(
         init_if_needed,
         payer = receiver,
         associated_token::mint = usdc_mint,
-        associated_token::authority = eda_vault
+        associated_token::authority = presale_escrow
     )]
-    pub eda_usdc_ata: Box<Account<'info, TokenAccount>>,
+    pub escrow_usdc_ata: Box<Account<'info, TokenAccount>>,
 
     // Receiver token account
     #[account(
@@ -127,106 +144,480 @@ pub struct BuyPresaleElw<'info> {
     pub price_update: Box<Account<'info, PriceUpdateV2>>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AllowlistProof {
+    pub allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
 pub fn buy(
     ctx: Context<BuyPresaleElw>,
     presale_type: PresaleType,
     amount_to_buy: u64,
     currency: Currency,
+    max_payment_amount: u64,
+    allowlist_proof: Option<AllowlistProof>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
-    let summary_account = &mut ctx.accounts.presale_summary_account;
-    let purchase_account = &mut ctx.accounts.receiver_purchase_account;
-
-    PRESALE_RULES.conditions(
+    let (round_index, payment_amount) = check_purchase(
+        &ctx.accounts.presale_config.rules,
+        &ctx.accounts.presale_summary_account,
+        &ctx.accounts.receiver_purchase_account,
+        ctx.accounts.receiver_allowlist_account.as_deref_mut(),
+        &ctx.accounts.receiver.key(),
+        presale_type,
         amount_to_buy,
-        summary_account.token_sold,
-        purchase_account.amount,
+        allowlist_proof,
     )?;
 
-    let unlock_time = PRESALE_RULES.get_unlock_time(presale_type);
-    let (payment_amount, eda_amount) =
-        PRESALE_RULES.calculate_payment_amount_and_eda_amount(amount_to_buy, presale_type);
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &mut ctx.accounts.presale_summary_account;
+    let purchase_account = &mut ctx.accounts.receiver_purchase_account;
 
     let receiver_usdc_ata_amount = ctx.accounts.receiver_usdc_ata.amount;
     let receiver = &ctx.accounts.receiver.to_account_info();
-    let eda_vault = &ctx.accounts.eda_vault.to_account_info();
-    let eda_usdc_ata = &ctx.accounts.eda_usdc_ata.to_account_info();
+    let presale_escrow = &ctx.accounts.presale_escrow.to_account_info();
     let token_program = &ctx.accounts.token_program.to_account_info();
-    let liquidity_wsol_ata = &ctx.accounts.liquidity_wsol_ata.to_account_info();
-    let liquidity_usdc_ata = &ctx.accounts.liquidity_usdc_ata.to_account_info();
+    let escrow_usdc_ata = &ctx.accounts.escrow_usdc_ata.to_account_info();
     let receiver_usdc_ata = &ctx.accounts.receiver_usdc_ata.to_account_info();
 
+    // payments stay in escrow until the presale ends, see release and refund
     if currency == Currency::USDC {
         require!(
-            receiver_usdc_ata_amount >= payment_amount + eda_amount,
+            payment_amount <= max_payment_amount,
+            CustomError::ExceedsTheMaximumPayment
+        );
+
+        require!(
+            receiver_usdc_ata_amount >= payment_amount,
             CustomError::InsufficientBalance,
         );
 
         transfer_token(
             token_program,
             receiver_usdc_ata,
-            liquidity_usdc_ata,
+            escrow_usdc_ata,
             receiver,
             payment_amount,
         )?;
 
-        transfer_token(
-            token_program,
-            receiver_usdc_ata,
-            eda_usdc_ata,
-            receiver,
-            eda_amount,
-        )?;
-
-        summary_account.usdc_sent_to_eda += eda_amount;
-        summary_account.usdc_sent_to_liquidity += payment_amount;
         summary_account.token_sold_for_usdc += amount_to_buy;
-        summary_account.usdc_raised += payment_amount + eda_amount;
+        summary_account.usdc_raised += payment_amount;
+        purchase_account.usdc_paid += payment_amount;
     } else if currency == Currency::SOL {
-        let feed_id = get_feed_id_from_hex(
-            "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+        let (price, exponent) = get_oracle_price(
+            &ctx.accounts.price_update,
+            &get_feed_id_from_hex(SOL_USD_FEED_ID)?,
+            rules.max_price_age,
+            rules.max_price_confidence,
         )?;
-        let price_update = &ctx.accounts.price_update;
-        let price_data = price_update.get_price_unchecked(&feed_id)?;
-        let price = price_data.price + price_data.conf as i64; // see: https://docs.pyth.network/price-feeds/use-real-time-data/solana
-        let sol_payment_amount = usdc_to_sol(payment_amount, price, price_data.exponent);
-        let sol_eda_amount = usdc_to_sol(eda_amount, price, price_data.exponent);
-
-        let sol_balance = **receiver.to_account_info().try_borrow_lamports()?;
+        let sol_amount = usdc_to_sol(payment_amount, price, exponent);
 
         require!(
-            sol_balance >= sol_payment_amount + sol_eda_amount,
-            CustomError::InsufficientBalance,
+            sol_amount <= max_payment_amount,
+            CustomError::ExceedsTheMaximumPayment
         );
 
-        transfer_sol(receiver, eda_vault, sol_eda_amount)?;
-        transfer_sol(receiver, liquidity_wsol_ata, sol_payment_amount)?;
+        let sol_balance = **receiver.to_account_info().try_borrow_lamports()?;
 
-        // Wrap SOL for liquidity pool
-        wrap_sol(token_program, liquidity_wsol_ata)?;
+        require!(sol_balance >= sol_amount, CustomError::InsufficientBalance);
+
+        transfer_sol(receiver, presale_escrow, sol_amount)?;
 
-        summary_account.sol_sent_to_eda += sol_eda_amount;
-        summary_account.sol_sent_to_liquidity += sol_payment_amount;
         summary_account.token_sold_for_sol += amount_to_buy;
-        summary_account.sol_raised += sol_payment_amount + sol_eda_amount;
+        summary_account.sol_raised += sol_amount;
+        purchase_account.sol_paid += sol_amount;
     } else {
         require!(false, CustomError::InvalidCurrency);
     }
 
+    credit_referral(
+        &ctx.accounts.presale_config.rules,
+        &mut ctx.accounts.presale_summary_account,
+        ctx.accounts
+            .referral_account
+            .as_deref_mut()
+            .map(|account| &mut **account),
+        referrer,
+        ctx.accounts.receiver.key(),
+        amount_to_buy,
+        payment_amount,
+    )?;
+
+    record_purchase(
+        &mut ctx.accounts.presale_config,
+        &mut ctx.accounts.presale_summary_account,
+        &mut ctx.accounts.receiver_purchase_account,
+        ctx.accounts.receiver.key(),
+        presale_type,
+        round_index,
+        amount_to_buy,
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(presale_type: PresaleType)]
+pub struct BuyPresaleElwWithToken<'info> {
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    // Payment mint and its registry entry
+    pub payment_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        seeds = [
+            b"payment_mint".as_ref(),
+            payment_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_mint_account: Box<Account<'info, PaymentMintAccount>>,
+
+    // Receiver presale account
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        space = get_account_size(PurchaseAccount::INIT_SPACE),
+        seeds = [
+            b"presale".as_ref(),
+            receiver.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_purchase_account: Box<Account<'info, PurchaseAccount>>,
+
+    // Receiver payment account, amount paid with this mint for refunds
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        space = get_account_size(PaymentAccount::INIT_SPACE),
+        seeds = [
+            b"presale_payment".as_ref(),
+            receiver.key().as_ref(),
+            payment_mint.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_payment_account: Box<Account<'info, PaymentAccount>>,
+
+    // Receiver allowlist account, only needed before the public phase
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        space = get_account_size(AllowlistAccount::INIT_SPACE),
+        seeds = [
+            b"allowlist".as_ref(),
+            receiver.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receiver_allowlist_account: Option<Box<Account<'info, AllowlistAccount>>>,
+
+    // Referrer account, only for referred purchases
+    #[account(
+        mut,
+        seeds = [
+            b"referral".as_ref(),
+            referral_account.referrer.as_ref(),
+        ],
+        bump,
+    )]
+    pub referral_account: Option<Box<Account<'info, ReferralAccount>>>,
+
+    // Presale config
+    #[account(
+        mut,
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        space = get_account_size(SummaryAccount::INIT_SPACE),
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    /// CHECK: Presale escrow vault
+    #[account(
+        seeds = [
+            b"presale_escrow".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = presale_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    // Receiver token account
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub receiver_token_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    // Official programs, token or Token-2022 program of the payment mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    // pyth program, only for mints with a price feed
+    pub price_update: Option<Box<Account<'info, PriceUpdateV2>>>,
+}
+
+pub fn buy_with_token(
+    ctx: Context<BuyPresaleElwWithToken>,
+    presale_type: PresaleType,
+    amount_to_buy: u64,
+    max_payment_amount: u64,
+    allowlist_proof: Option<AllowlistProof>,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.payment_mint_account.is_enabled,
+        CustomError::PaymentMintIsDisabled
+    );
+
+    let (round_index, payment_amount) = check_purchase(
+        &ctx.accounts.presale_config.rules,
+        &ctx.accounts.presale_summary_account,
+        &ctx.accounts.receiver_purchase_account,
+        ctx.accounts.receiver_allowlist_account.as_deref_mut(),
+        &ctx.accounts.receiver.key(),
+        presale_type,
+        amount_to_buy,
+        allowlist_proof,
+    )?;
+
+    let rules = &ctx.accounts.presale_config.rules;
+    let payment_mint_account = &mut ctx.accounts.payment_mint_account;
+
+    // stablecoins are pegged 1:1 to USD
+    let (price, exponent) = match payment_mint_account.feed_id {
+        Some(feed_id) => get_oracle_price(
+            ctx.accounts
+                .price_update
+                .as_ref()
+                .ok_or(CustomError::WrongAccountGiven)?,
+            &feed_id,
+            rules.max_price_age,
+            rules.max_price_confidence,
+        )?,
+        None => (1, 0),
+    };
+    let token_amount = usdc_to_token(
+        payment_amount,
+        price,
+        exponent,
+        payment_mint_account.decimals,
+    );
+
+    require!(
+        token_amount <= max_payment_amount,
+        CustomError::ExceedsTheMaximumPayment
+    );
+
+    require!(
+        ctx.accounts.receiver_token_ata.amount >= token_amount,
+        CustomError::InsufficientBalance,
+    );
+
+    let escrow_balance = ctx.accounts.escrow_token_ata.amount;
+
+    // payments stay in escrow until the presale ends, see release and refund
+    transfer_token_checked(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.receiver_token_ata.to_account_info(),
+        &ctx.accounts.payment_mint.to_account_info(),
+        &ctx.accounts.escrow_token_ata.to_account_info(),
+        &ctx.accounts.receiver.to_account_info(),
+        token_amount,
+        payment_mint_account.decimals,
+    )?;
+
+    // Token-2022 transfer fees are not part of the refundable amount
+    ctx.accounts.escrow_token_ata.reload()?;
+    let received_amount = ctx.accounts.escrow_token_ata.amount - escrow_balance;
+
+    payment_mint_account.token_sold += amount_to_buy;
+    payment_mint_account.raised += received_amount;
+
+    let receiver_payment_account = &mut ctx.accounts.receiver_payment_account;
+    receiver_payment_account.amount += received_amount;
+    receiver_payment_account.token_bought += amount_to_buy;
+
+    credit_referral(
+        &ctx.accounts.presale_config.rules,
+        &mut ctx.accounts.presale_summary_account,
+        ctx.accounts
+            .referral_account
+            .as_deref_mut()
+            .map(|account| &mut **account),
+        referrer,
+        ctx.accounts.receiver.key(),
+        amount_to_buy,
+        payment_amount,
+    )?;
+
+    record_purchase(
+        &mut ctx.accounts.presale_config,
+        &mut ctx.accounts.presale_summary_account,
+        &mut ctx.accounts.receiver_purchase_account,
+        ctx.accounts.receiver.key(),
+        presale_type,
+        round_index,
+        amount_to_buy,
+    );
+
+    Ok(())
+}
+
+// Selects the active round and checks the purchase against the presale rules,
+// returns the round index and the payment amount in USDC
+#[allow(clippy::too_many_arguments)]
+fn check_purchase(
+    rules: &PresaleRules,
+    summary_account: &SummaryAccount,
+    purchase_account: &PurchaseAccount,
+    allowlist_account: Option<&mut Account<AllowlistAccount>>,
+    receiver: &Pubkey,
+    presale_type: PresaleType,
+    amount_to_buy: u64,
+    allowlist_proof: Option<AllowlistProof>,
+) -> Result<(usize, u64)> {
+    // rounds are selected by time, one at a time
+    let round_index = rules.get_active_round()?;
+
+    // bonuses credited to referrers are reserved from the allocation
+    rules.conditions(
+        round_index,
+        amount_to_buy,
+        summary_account.token_sold + summary_account.referral_bonus_amount,
+        summary_account.round_token_sold[round_index],
+        purchase_account.round_amounts[round_index],
+    )?;
+
+    if rules.is_allowlist_phase() {
+        let allowlist_proof = allowlist_proof.ok_or(CustomError::NotInAllowlist)?;
+        let allowlist_account = allowlist_account.ok_or(CustomError::WrongAccountGiven)?;
+
+        require!(
+            rules.is_allowlisted(receiver, allowlist_proof.allocation, &allowlist_proof.proof),
+            CustomError::NotInAllowlist
+        );
+
+        require!(
+            allowlist_proof.allocation == 0
+                || allowlist_account.purchased_amount + amount_to_buy <= allowlist_proof.allocation,
+            CustomError::ExceedsTheAllowlistAllocation
+        );
+
+        allowlist_account.purchased_amount += amount_to_buy;
+    }
+
+    let payment_amount = rules.calculate_payment_amount(amount_to_buy, presale_type, round_index);
+
+    Ok((round_index, payment_amount))
+}
+
+fn credit_referral(
+    rules: &PresaleRules,
+    summary_account: &mut SummaryAccount,
+    referral_account: Option<&mut ReferralAccount>,
+    referrer: Option<Pubkey>,
+    receiver: Pubkey,
+    amount_to_buy: u64,
+    payment_amount: u64,
+) -> Result<()> {
+    let Some(referrer) = referrer else {
+        return Ok(());
+    };
+    let referral_account = referral_account.ok_or(CustomError::WrongAccountGiven)?;
+
+    require!(
+        referral_account.referrer == referrer && referrer != receiver,
+        CustomError::InvalidReferrer
+    );
+
+    let bonus = apply_bps(
+        amount_to_buy,
+        rules.referral_bonus_percentage,
+        Rounding::Floor,
+    );
+
+    require!(
+        summary_account.token_sold + summary_account.referral_bonus_amount + amount_to_buy + bonus
+            <= rules.total_amount,
+        CustomError::ExceedsTheRemainingAmount
+    );
+
+    summary_account.referral_bonus_amount += bonus;
+    referral_account.purchase_count += 1;
+    referral_account.token_volume += amount_to_buy;
+    referral_account.usdc_volume += payment_amount;
+    referral_account.earned_amount += bonus;
+
+    emit!(ReferralPurchaseEvent {
+        referrer,
+        receiver,
+        amount: amount_to_buy,
+        usdc_amount: payment_amount,
+        bonus,
+        token_volume: referral_account.token_volume,
+        usdc_volume: referral_account.usdc_volume,
+        earned_amount: referral_account.earned_amount,
+    });
+
+    Ok(())
+}
+
+fn record_purchase(
+    presale_config: &mut PresaleConfigAccount,
+    summary_account: &mut SummaryAccount,
+    purchase_account: &mut PurchaseAccount,
+    receiver: Pubkey,
+    presale_type: PresaleType,
+    round_index: usize,
+    amount_to_buy: u64,
+) {
     // presale summary account
     summary_account.token_sold += amount_to_buy;
+    summary_account.round_token_sold[round_index] += amount_to_buy;
     // write for the first time
     if summary_account.total_amount == 0 {
-        summary_account.total_amount = PRESALE_RULES.total_amount;
+        summary_account.total_amount = presale_config.rules.total_amount;
+    }
+    // config can not be changed after the first purchase
+    if !presale_config.is_frozen {
+        presale_config.is_frozen = true;
     }
     // receiver purchase account
     purchase_account.amount += amount_to_buy;
-    purchase_account.unlock_time = unlock_time;
+    purchase_account.round_amounts[round_index] += amount_to_buy;
+    purchase_account.unlock_time = presale_config.rules.get_unlock_time(presale_type);
     purchase_account.presale_type = presale_type as u8;
 
     emit!(BuyPresaleTokenEvent {
-        receiver: ctx.accounts.receiver.key(),
+        receiver,
         amount: amount_to_buy,
+        round: round_index as u8,
     });
-
-    Ok(())
 }
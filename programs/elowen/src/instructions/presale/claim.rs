@@ -4,7 +4,7 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{constants::*, enums::*, functions::*, state::*};
+use crate::{enums::*, functions::*, state::*};
 
 #[derive(Accounts)]
 #[instruction(presale_type: PresaleType)]
@@ -25,7 +25,25 @@ pub struct ClaimPresaleElw<'info> {
     #[account(address = platform.elw_mint)]
     pub elw_mint: Account<'info, Mint>,
 
-    // Receiver presale account
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Account<'info, PresaleConfigAccount>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Account<'info, SummaryAccount>,
+
+    // Receiver presale account, not needed to redeem receipts only
     #[account(
         mut,
         seeds = [
@@ -35,7 +53,45 @@ pub struct ClaimPresaleElw<'info> {
         ],
         bump,
     )]
-    pub receiver_purchase_account: Account<'info, PurchaseAccount>,
+    pub receiver_purchase_account: Option<Account<'info, PurchaseAccount>>,
+
+    // Receipt mint and receiver receipts, only to redeem receipts
+    #[account(
+        mut,
+        seeds = [
+            b"presale_receipt".as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receipt_mint: Option<Account<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = receiver
+    )]
+    pub receiver_receipt_ata: Option<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [
+            b"presale_receipt_summary".as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receipt_summary_account: Option<Account<'info, ReceiptSummaryAccount>>,
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        space = get_account_size(ReceiptClaimAccount::INIT_SPACE),
+        seeds = [
+            b"presale_receipt_claim".as_ref(),
+            receiver.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_receipt_claim_account: Option<Account<'info, ReceiptClaimAccount>>,
 
     /// CHECK: Presale vault
     #[account(
@@ -67,23 +123,76 @@ pub struct ClaimPresaleElw<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-pub fn claim(ctx: Context<ClaimPresaleElw>) -> Result<()> {
-    let purchase_account = &mut ctx.accounts.receiver_purchase_account;
+pub fn claim(ctx: Context<ClaimPresaleElw>, presale_type: PresaleType) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
 
     require!(
-        PRESALE_RULES.is_presale_ended(),
-        CustomError::PresaleIsNotEnded
+        rules.is_soft_cap_reached(ctx.accounts.presale_summary_account.token_sold),
+        CustomError::SoftCapNotReached
     );
 
     let current_time = Clock::get()?.unix_timestamp;
 
+    // held receipts are burned first, then redeemed 1:1 at the vested ratio of their lockup type
+    let receipt_amount = match (
+        &ctx.accounts.receipt_mint,
+        &ctx.accounts.receiver_receipt_ata,
+        &mut ctx.accounts.receipt_summary_account,
+        &mut ctx.accounts.receiver_receipt_claim_account,
+    ) {
+        (
+            Some(receipt_mint),
+            Some(receiver_receipt_ata),
+            Some(receipt_summary_account),
+            Some(receiver_receipt_claim_account),
+        ) => {
+            let burn_amount = receiver_receipt_ata.amount;
+
+            if burn_amount > 0 {
+                burn_token(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &receipt_mint.to_account_info(),
+                    &receiver_receipt_ata.to_account_info(),
+                    &ctx.accounts.receiver.to_account_info(),
+                    burn_amount,
+                )?;
+
+                receipt_summary_account.burned_amount += burn_amount;
+                receiver_receipt_claim_account.burned_amount += burn_amount;
+            }
+
+            let schedule = rules.get_vesting_schedule(rules.get_unlock_time(presale_type));
+            let receipt_amount =
+                receiver_receipt_claim_account.get_redeemable_amount(&schedule, current_time);
+
+            receipt_summary_account.redeemed_amount += receipt_amount;
+            receiver_receipt_claim_account.redeemed_amount += receipt_amount;
+
+            receipt_amount
+        }
+        _ => 0,
+    };
+
+    let purchase_amount = match &ctx.accounts.receiver_purchase_account {
+        Some(purchase_account) => {
+            require!(
+                receipt_amount > 0 || !purchase_account.claimed,
+                CustomError::TokensAlreadyClaimed
+            );
+            purchase_account.get_claimable_amount(rules, current_time)
+        }
+        None => 0,
+    };
+
+    let claimable_amount = purchase_amount + receipt_amount;
+
     require!(
-        current_time >= purchase_account.unlock_time,
+        claimable_amount > 0,
         CustomError::CannotClaimUntilUnlockTime
     );
 
-    require!(!purchase_account.claimed, CustomError::TokensAlreadyClaimed);
-
     let receiver = &ctx.accounts.receiver.to_account_info();
     let presale_vault = &ctx.accounts.presale_vault.to_account_info();
     let token_program = &ctx.accounts.token_program.to_account_info();
@@ -97,10 +206,13 @@ pub fn claim(ctx: Context<ClaimPresaleElw>) -> Result<()> {
         presale_token_ata,
         receiver_token_ata,
         presale_vault,
-        purchase_account.amount,
+        claimable_amount,
     )?;
 
-    purchase_account.claimed = true;
+    if let Some(purchase_account) = &mut ctx.accounts.receiver_purchase_account {
+        purchase_account.claimed_amount += purchase_amount;
+        purchase_account.claimed = purchase_account.claimed_amount >= purchase_account.amount;
+    }
 
     if ctx.accounts.presale_token_ata.amount == 0 {
         close_token_account_with_pda_key(
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{enums::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct InitializePresaleConfig<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Presale config
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(PresaleConfigAccount::INIT_SPACE),
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Account<'info, PresaleConfigAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(ctx: Context<InitializePresaleConfig>, rules: PresaleRules) -> Result<()> {
+    rules.validate()?;
+
+    let presale_config = &mut ctx.accounts.presale_config;
+    presale_config.rules = rules;
+    presale_config.is_frozen = false;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePresaleConfig<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Presale config
+    #[account(
+        mut,
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Account<'info, PresaleConfigAccount>,
+}
+
+pub fn update_config(ctx: Context<UpdatePresaleConfig>, rules: PresaleRules) -> Result<()> {
+    let presale_config = &mut ctx.accounts.presale_config;
+
+    // rules can not be changed once the sale opens
+    require!(
+        !presale_config.is_frozen && !presale_config.rules.is_presale_started(),
+        CustomError::PresaleConfigIsFrozen
+    );
+
+    rules.validate()?;
+
+    presale_config.rules = rules;
+
+    Ok(())
+}
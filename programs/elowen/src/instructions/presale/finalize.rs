@@ -0,0 +1,571 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+use mpl_token_metadata::ID as METADATA_ID;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use raydium_cp_swap::{
+    program::RaydiumCpSwap,
+    states::{AmmConfig, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
+};
+use raydium_locking_cpi::{program::RaydiumLiquidityLocking, states::LOCKED_LIQUIDITY_SEED};
+
+use crate::{
+    constants::*,
+    enums::*,
+    events::ElwBurnEvent,
+    functions::*,
+    instructions::liquidity::{create_locked_pool, LockedPoolAccounts},
+    math::{mul_div, Rounding},
+    state::*,
+};
+
+// Both the SOL and the USDC pool are created and locked here, so the presale
+// liquidity can not be left half bootstrapped
+#[derive(Accounts)]
+pub struct FinalizePresale<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Raydium CPMM program
+    #[account(
+        address = raydium_cp_swap::ID,
+    )]
+    pub cp_swap_program: Program<'info, RaydiumCpSwap>,
+
+    // create pool fee account
+    #[account(
+        mut,
+        address = raydium_cp_swap::create_pool_fee_reveiver::ID,
+    )]
+    pub create_pool_fee: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Which config the pools belong to, fixed by the presale config
+    #[account(
+        address = presale_config.rules.amm_config @ CustomError::WrongAccountGiven,
+    )]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        seeds = [
+            raydium_cp_swap::AUTH_SEED.as_bytes(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub cp_swap_authority: UncheckedAccount<'info>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        mut,
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    /// CHECK: Presale vault
+    #[account(
+        seeds = [
+            b"presale".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Presale),
+    )]
+    pub presale_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Platform PDA
+    #[account(
+        seeds = [
+            b"platform".as_ref(),
+        ],
+        bump,
+    )]
+    pub platform: Box<Account<'info, PlatformAccount>>,
+    /// CHECK: Liquidity vault
+    #[account(
+        mut,
+        seeds = [
+            b"liquidity".as_ref(),
+        ],
+        bump,
+    )]
+    pub liquidity_vault: UncheckedAccount<'info>,
+
+    // token mints
+    #[account(
+        mut,
+        address = platform.elw_mint,
+    )]
+    pub elw_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = WSOL_MINT)]
+    pub wsol_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = USDC_MINT)]
+    pub usdc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // token accounts
+    #[account(
+        mut,
+        associated_token::mint = elw_mint,
+        associated_token::authority = liquidity_vault
+    )]
+    pub liquidity_elw_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = liquidity_vault
+    )]
+    pub liquidity_wsol_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = liquidity_vault
+    )]
+    pub liquidity_usdc_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: SOL pool state, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+            elw_mint.key().as_ref(),
+            wsol_mint.key().as_ref(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub sol_pool_state: UncheckedAccount<'info>,
+    /// CHECK: SOL pool observations, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            OBSERVATION_SEED.as_bytes(),
+            sol_pool_state.key().as_ref(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub sol_observation_state: UncheckedAccount<'info>,
+    /// CHECK: ELW vault of the SOL pool, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            sol_pool_state.key().as_ref(),
+            elw_mint.key().as_ref()
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub sol_elw_vault: UncheckedAccount<'info>,
+    /// CHECK: WSOL vault of the SOL pool, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            sol_pool_state.key().as_ref(),
+            wsol_mint.key().as_ref()
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub sol_quote_vault: UncheckedAccount<'info>,
+    /// CHECK: SOL pool lp mint, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_LP_MINT_SEED.as_bytes(),
+            sol_pool_state.key().as_ref(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub sol_lp_mint: UncheckedAccount<'info>,
+    /// CHECK: creator lp ATA of the SOL pool, init by cp-swap
+    #[account(mut)]
+    pub liquidity_sol_lp_token_ata: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = get_account_size(LpStateAccount::INIT_SPACE),
+        seeds = [
+            b"lp_state".as_ref(),
+            wsol_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sol_lp_state: Box<Account<'info, LpStateAccount>>,
+
+    /// CHECK: USDC pool state, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+            elw_mint.key().as_ref(),
+            usdc_mint.key().as_ref(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub usdc_pool_state: UncheckedAccount<'info>,
+    /// CHECK: USDC pool observations, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            OBSERVATION_SEED.as_bytes(),
+            usdc_pool_state.key().as_ref(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub usdc_observation_state: UncheckedAccount<'info>,
+    /// CHECK: ELW vault of the USDC pool, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            usdc_pool_state.key().as_ref(),
+            elw_mint.key().as_ref()
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub usdc_elw_vault: UncheckedAccount<'info>,
+    /// CHECK: USDC vault of the USDC pool, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            usdc_pool_state.key().as_ref(),
+            usdc_mint.key().as_ref()
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub usdc_quote_vault: UncheckedAccount<'info>,
+    /// CHECK: USDC pool lp mint, init by cp-swap
+    #[account(
+        mut,
+        seeds = [
+            POOL_LP_MINT_SEED.as_bytes(),
+            usdc_pool_state.key().as_ref(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub usdc_lp_mint: UncheckedAccount<'info>,
+    /// CHECK: creator lp ATA of the USDC pool, init by cp-swap
+    #[account(mut)]
+    pub liquidity_usdc_lp_token_ata: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = get_account_size(LpStateAccount::INIT_SPACE),
+        seeds = [
+            b"lp_state".as_ref(),
+            usdc_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub usdc_lp_state: Box<Account<'info, LpStateAccount>>,
+
+    // Official programs
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // locking program
+    pub locking_program: Program<'info, RaydiumLiquidityLocking>,
+    /// CHECK: the authority of token vault that cp is locked
+    #[account(
+        seeds = [
+            raydium_locking_cpi::LOCK_CP_AUTH_SEED.as_bytes(),
+        ],
+        bump,
+        seeds::program = locking_program.key(),
+    )]
+    pub locking_authority: UncheckedAccount<'info>,
+    /// CHECK: This is metadata program
+    #[account(
+        address = METADATA_ID,
+    )]
+    pub metadata_program: AccountInfo<'info>,
+
+    /// CHECK: Unique fee nft mint of the SOL pool, init by locking program
+    #[account(mut)]
+    pub sol_fee_nft_mint: Signer<'info>,
+    /// CHECK: Locked liquidity of the SOL pool, init by locking program
+    #[account(
+        mut,
+        seeds = [
+            LOCKED_LIQUIDITY_SEED.as_bytes(),
+            sol_fee_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = locking_program.key(),
+    )]
+    pub sol_locked_liquidity: UncheckedAccount<'info>,
+    /// CHECK: locked lp token account of the SOL pool, init before locking program cpi call
+    #[account(mut)]
+    pub sol_locked_lp_token_ata: UncheckedAccount<'info>,
+    /// CHECK: SOL pool fee nft token account, init by locking program
+    #[account(mut)]
+    pub sol_fee_nft_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = get_account_size(LockedLpStateAccount::INIT_SPACE),
+        seeds = [
+            b"locked_lp_state".as_ref(),
+            sol_fee_nft_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub sol_locked_lp_state: Box<Account<'info, LockedLpStateAccount>>,
+    /// CHECK: SOL pool fee nft metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            METADATA_ID.as_ref(),
+            sol_fee_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = METADATA_ID,
+    )]
+    pub sol_metadata_account: AccountInfo<'info>,
+
+    /// CHECK: Unique fee nft mint of the USDC pool, init by locking program
+    #[account(mut)]
+    pub usdc_fee_nft_mint: Signer<'info>,
+    /// CHECK: Locked liquidity of the USDC pool, init by locking program
+    #[account(
+        mut,
+        seeds = [
+            LOCKED_LIQUIDITY_SEED.as_bytes(),
+            usdc_fee_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = locking_program.key(),
+    )]
+    pub usdc_locked_liquidity: UncheckedAccount<'info>,
+    /// CHECK: locked lp token account of the USDC pool, init before locking program cpi call
+    #[account(mut)]
+    pub usdc_locked_lp_token_ata: UncheckedAccount<'info>,
+    /// CHECK: USDC pool fee nft token account, init by locking program
+    #[account(mut)]
+    pub usdc_fee_nft_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = get_account_size(LockedLpStateAccount::INIT_SPACE),
+        seeds = [
+            b"locked_lp_state".as_ref(),
+            usdc_fee_nft_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub usdc_locked_lp_state: Box<Account<'info, LockedLpStateAccount>>,
+    /// CHECK: USDC pool fee nft metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata".as_ref(),
+            METADATA_ID.as_ref(),
+            usdc_fee_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = METADATA_ID,
+    )]
+    pub usdc_metadata_account: AccountInfo<'info>,
+
+    // pyth program, prices the SOL side
+    pub price_update: Box<Account<'info, PriceUpdateV2>>,
+}
+
+pub fn finalize(ctx: Context<FinalizePresale>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &mut ctx.accounts.presale_summary_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    require!(
+        rules.is_soft_cap_reached(summary_account.token_sold),
+        CustomError::SoftCapNotReached
+    );
+
+    // raised funds are moved to the liquidity vault by release
+    require!(
+        summary_account.is_funds_released,
+        CustomError::PresaleFundsNotReleased
+    );
+
+    // burn what is left in the presale vault
+    if !summary_account.is_unsold_tokens_burned {
+        let burn_amount = summary_account.get_unsold_amount(rules);
+
+        if burn_amount > 0 {
+            burn_token_with_pda_key(
+                "presale",
+                ctx.bumps.presale_vault,
+                &ctx.accounts.token_program,
+                &ctx.accounts.elw_mint.to_account_info(),
+                &ctx.accounts.presale_token_ata.to_account_info(),
+                &ctx.accounts.presale_vault,
+                burn_amount,
+            )?;
+
+            emit!(ElwBurnEvent {
+                process: "presale".to_string(),
+                amount: burn_amount,
+            });
+        }
+
+        summary_account.is_unsold_tokens_burned = true;
+    }
+
+    // quote sides are what release sent to liquidity, donations to the vault are ignored
+    let sol_amount = summary_account.sol_sent_to_liquidity;
+    let usdc_amount = summary_account.usdc_sent_to_liquidity;
+
+    // SOL at the low end of its confidence interval, so the pool never opens ELW below the final price
+    let sol_usdc_amount = if sol_amount > 0 {
+        let (price, confidence, exponent) = get_oracle_price_with_confidence(
+            &ctx.accounts.price_update,
+            &get_feed_id_from_hex(SOL_USD_FEED_ID)?,
+            rules.max_price_age,
+            rules.max_price_confidence,
+        )?;
+        token_to_usdc(
+            sol_amount,
+            price.saturating_sub(confidence).max(0),
+            exponent,
+            ctx.accounts.wsol_mint.decimals,
+        )
+    } else {
+        0
+    };
+
+    // ELW sides at the final presale price
+    let final_price = rules.get_final_price();
+    let sol_elw_amount = mul_div(sol_usdc_amount, 10u64.pow(9), final_price, Rounding::Floor)
+        .ok_or(CustomError::InsufficientLiquidity)?;
+    let usdc_elw_amount = mul_div(usdc_amount, 10u64.pow(9), final_price, Rounding::Floor)
+        .ok_or(CustomError::InsufficientLiquidity)?;
+
+    // a currency nobody paid with gets no pool, its accounts are left untouched
+    require!(
+        (sol_amount > 0 || usdc_amount > 0)
+            && (sol_amount == 0 || sol_elw_amount > 0)
+            && (usdc_amount == 0 || usdc_elw_amount > 0)
+            && ctx.accounts.liquidity_elw_token_ata.amount
+                >= sol_elw_amount.saturating_add(usdc_elw_amount),
+        CustomError::InsufficientLiquidity
+    );
+
+    if sol_amount > 0 {
+        create_locked_pool(
+            &LockedPoolAccounts {
+                payer: &ctx.accounts.payer.to_account_info(),
+                liquidity_vault: &ctx.accounts.liquidity_vault.to_account_info(),
+                liquidity_vault_bump: ctx.bumps.liquidity_vault,
+                amm_config: &ctx.accounts.amm_config,
+                cp_swap_program: &ctx.accounts.cp_swap_program.to_account_info(),
+                cp_swap_authority: &ctx.accounts.cp_swap_authority.to_account_info(),
+                create_pool_fee: &ctx.accounts.create_pool_fee.to_account_info(),
+                pool_state: &ctx.accounts.sol_pool_state.to_account_info(),
+                observation_state: &ctx.accounts.sol_observation_state.to_account_info(),
+                elw_mint: &ctx.accounts.elw_mint.to_account_info(),
+                quote_mint: &ctx.accounts.wsol_mint.to_account_info(),
+                elw_vault: &ctx.accounts.sol_elw_vault.to_account_info(),
+                quote_vault: &ctx.accounts.sol_quote_vault.to_account_info(),
+                lp_mint: &ctx.accounts.sol_lp_mint.to_account_info(),
+                liquidity_elw_token_ata: &ctx.accounts.liquidity_elw_token_ata.to_account_info(),
+                liquidity_quote_token_ata: &ctx.accounts.liquidity_wsol_ata.to_account_info(),
+                liquidity_lp_token_ata: &ctx.accounts.liquidity_sol_lp_token_ata.to_account_info(),
+                fee_nft_mint: &ctx.accounts.sol_fee_nft_mint.to_account_info(),
+                fee_nft_account: &ctx.accounts.sol_fee_nft_account.to_account_info(),
+                locking_program: &ctx.accounts.locking_program.to_account_info(),
+                locking_authority: &ctx.accounts.locking_authority.to_account_info(),
+                locked_liquidity: &ctx.accounts.sol_locked_liquidity.to_account_info(),
+                locked_lp_token_ata: &ctx.accounts.sol_locked_lp_token_ata.to_account_info(),
+                metadata_account: &ctx.accounts.sol_metadata_account,
+                metadata_program: &ctx.accounts.metadata_program,
+                rent: &ctx.accounts.rent.to_account_info(),
+                token_program: &ctx.accounts.token_program.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            },
+            &mut ctx.accounts.sol_lp_state,
+            &mut ctx.accounts.sol_locked_lp_state,
+            Currency::SOL,
+            sol_elw_amount,
+            sol_amount,
+            0, // open immediately
+        )?;
+    }
+
+    if usdc_amount > 0 {
+        create_locked_pool(
+            &LockedPoolAccounts {
+                payer: &ctx.accounts.payer.to_account_info(),
+                liquidity_vault: &ctx.accounts.liquidity_vault.to_account_info(),
+                liquidity_vault_bump: ctx.bumps.liquidity_vault,
+                amm_config: &ctx.accounts.amm_config,
+                cp_swap_program: &ctx.accounts.cp_swap_program.to_account_info(),
+                cp_swap_authority: &ctx.accounts.cp_swap_authority.to_account_info(),
+                create_pool_fee: &ctx.accounts.create_pool_fee.to_account_info(),
+                pool_state: &ctx.accounts.usdc_pool_state.to_account_info(),
+                observation_state: &ctx.accounts.usdc_observation_state.to_account_info(),
+                elw_mint: &ctx.accounts.elw_mint.to_account_info(),
+                quote_mint: &ctx.accounts.usdc_mint.to_account_info(),
+                elw_vault: &ctx.accounts.usdc_elw_vault.to_account_info(),
+                quote_vault: &ctx.accounts.usdc_quote_vault.to_account_info(),
+                lp_mint: &ctx.accounts.usdc_lp_mint.to_account_info(),
+                liquidity_elw_token_ata: &ctx.accounts.liquidity_elw_token_ata.to_account_info(),
+                liquidity_quote_token_ata: &ctx.accounts.liquidity_usdc_ata.to_account_info(),
+                liquidity_lp_token_ata: &ctx.accounts.liquidity_usdc_lp_token_ata.to_account_info(),
+                fee_nft_mint: &ctx.accounts.usdc_fee_nft_mint.to_account_info(),
+                fee_nft_account: &ctx.accounts.usdc_fee_nft_account.to_account_info(),
+                locking_program: &ctx.accounts.locking_program.to_account_info(),
+                locking_authority: &ctx.accounts.locking_authority.to_account_info(),
+                locked_liquidity: &ctx.accounts.usdc_locked_liquidity.to_account_info(),
+                locked_lp_token_ata: &ctx.accounts.usdc_locked_lp_token_ata.to_account_info(),
+                metadata_account: &ctx.accounts.usdc_metadata_account,
+                metadata_program: &ctx.accounts.metadata_program,
+                rent: &ctx.accounts.rent.to_account_info(),
+                token_program: &ctx.accounts.token_program.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+                associated_token_program: &ctx.accounts.associated_token_program.to_account_info(),
+            },
+            &mut ctx.accounts.usdc_lp_state,
+            &mut ctx.accounts.usdc_locked_lp_state,
+            Currency::USDC,
+            usdc_elw_amount,
+            usdc_amount,
+            0, // open immediately
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{enums::*, functions::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(presale_type: PresaleType)]
+pub struct MigratePurchaseAccount<'info> {
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    /// CHECK: Receiver presale account in the legacy layout, checked in the instruction
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"presale".as_ref(),
+            receiver.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_purchase_account: UncheckedAccount<'info>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate(ctx: Context<MigratePurchaseAccount>) -> Result<()> {
+    let receiver = &ctx.accounts.receiver.to_account_info();
+    let purchase_account = &ctx.accounts.receiver_purchase_account.to_account_info();
+
+    let legacy_size = get_account_size(LegacyPurchaseAccount::INIT_SPACE);
+    let new_size = get_account_size(PurchaseAccount::INIT_SPACE);
+
+    let legacy = {
+        let data = purchase_account.try_borrow_data()?;
+        require!(
+            data.len() < new_size && data.starts_with(PurchaseAccount::DISCRIMINATOR),
+            CustomError::PurchaseAccountAlreadyMigrated
+        );
+        LegacyPurchaseAccount::deserialize(&mut &data[8..legacy_size])?
+    };
+
+    let rent_difference = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(purchase_account.lamports());
+
+    if rent_difference > 0 {
+        transfer_sol(receiver, purchase_account, rent_difference)?;
+    }
+
+    purchase_account.realloc(new_size, true)?;
+
+    let migrated = PurchaseAccount::from_legacy(legacy);
+    let mut data = purchase_account.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateSummaryAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Total sales account in the legacy layout, checked in the instruction
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: UncheckedAccount<'info>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_summary(ctx: Context<MigrateSummaryAccount>) -> Result<()> {
+    let payer = &ctx.accounts.payer.to_account_info();
+    let summary_account = &ctx.accounts.presale_summary_account.to_account_info();
+
+    let legacy_size = get_account_size(LegacySummaryAccount::INIT_SPACE);
+    let new_size = get_account_size(SummaryAccount::INIT_SPACE);
+
+    let legacy = {
+        let data = summary_account.try_borrow_data()?;
+        require!(
+            data.len() < new_size && data.starts_with(SummaryAccount::DISCRIMINATOR),
+            CustomError::SummaryAccountAlreadyMigrated
+        );
+        LegacySummaryAccount::deserialize(&mut &data[8..legacy_size])?
+    };
+
+    let rent_difference = Rent::get()?
+        .minimum_balance(new_size)
+        .saturating_sub(summary_account.lamports());
+
+    if rent_difference > 0 {
+        transfer_sol(payer, summary_account, rent_difference)?;
+    }
+
+    summary_account.realloc(new_size, true)?;
+
+    let migrated = SummaryAccount::from_legacy(legacy);
+    let mut data = summary_account.try_borrow_mut_data()?;
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
@@ -1,7 +1,25 @@
 pub mod burn;
 pub mod buy;
 pub mod claim;
+pub mod config;
+pub mod finalize;
+pub mod migrate;
+pub mod payment;
+pub mod receipt;
+pub mod referral;
+pub mod refund;
+pub mod release;
+pub mod transfer;
 
 pub use burn::*;
 pub use buy::*;
 pub use claim::*;
+pub use config::*;
+pub use finalize::*;
+pub use migrate::*;
+pub use payment::*;
+pub use receipt::*;
+pub use referral::*;
+pub use refund::*;
+pub use release::*;
+pub use transfer::*;
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{constants::*, enums::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct RegisterPaymentMint<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Platform PDA
+    #[account(
+        seeds = [
+            b"platform".as_ref(),
+        ],
+        bump,
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
+    // Payment mint, USDC and SOL have their own currencies
+    #[account(
+        constraint = payment_mint.key() != USDC_MINT
+            && payment_mint.key() != WSOL_MINT
+            && payment_mint.key() != platform.elw_mint @ CustomError::InvalidPaymentMint,
+    )]
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(PaymentMintAccount::INIT_SPACE),
+        seeds = [
+            b"payment_mint".as_ref(),
+            payment_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_mint_account: Account<'info, PaymentMintAccount>,
+
+    /// CHECK: Presale escrow vault
+    #[account(
+        seeds = [
+            b"presale_escrow".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_escrow: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = presale_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Official programs, token or Token-2022 program of the payment mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn register_payment_mint(
+    ctx: Context<RegisterPaymentMint>,
+    feed_id: Option<[u8; 32]>,
+) -> Result<()> {
+    let payment_mint_account = &mut ctx.accounts.payment_mint_account;
+
+    payment_mint_account.mint = ctx.accounts.payment_mint.key();
+    payment_mint_account.decimals = ctx.accounts.payment_mint.decimals;
+    payment_mint_account.feed_id = feed_id;
+    payment_mint_account.is_enabled = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePaymentMint<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"payment_mint".as_ref(),
+            payment_mint_account.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_mint_account: Account<'info, PaymentMintAccount>,
+}
+
+pub fn update_payment_mint(
+    ctx: Context<UpdatePaymentMint>,
+    feed_id: Option<[u8; 32]>,
+    is_enabled: bool,
+) -> Result<()> {
+    let payment_mint_account = &mut ctx.accounts.payment_mint_account;
+
+    payment_mint_account.feed_id = feed_id;
+    payment_mint_account.is_enabled = is_enabled;
+
+    Ok(())
+}
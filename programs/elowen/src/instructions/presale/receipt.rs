@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{enums::*, events::TokenizePresalePositionEvent, functions::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(presale_type: PresaleType)]
+pub struct TokenizePresalePosition<'info> {
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    // Receiver presale account
+    #[account(
+        mut,
+        seeds = [
+            b"presale".as_ref(),
+            receiver.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_purchase_account: Box<Account<'info, PurchaseAccount>>,
+
+    // Receipt mint of the lockup type, 1:1 with ELW
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        mint::decimals = 9,
+        mint::authority = receipt_mint,
+        seeds = [
+            b"presale_receipt".as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    // Receipts minted and redeemed of the lockup type
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        space = get_account_size(ReceiptSummaryAccount::INIT_SPACE),
+        seeds = [
+            b"presale_receipt_summary".as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receipt_summary_account: Box<Account<'info, ReceiptSummaryAccount>>,
+
+    // Receiver receipt token account
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = receiver
+    )]
+    pub receiver_receipt_ata: Box<Account<'info, TokenAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn tokenize(
+    ctx: Context<TokenizePresalePosition>,
+    presale_type: PresaleType,
+    amount: u64,
+) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let purchase_account = &mut ctx.accounts.receiver_purchase_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    // receipts can not be refunded
+    require!(
+        rules.is_soft_cap_reached(ctx.accounts.presale_summary_account.token_sold),
+        CustomError::SoftCapNotReached
+    );
+
+    require!(
+        amount > 0 && amount <= purchase_account.amount - purchase_account.claimed_amount,
+        CustomError::InsufficientBalance
+    );
+
+    mint_token_with_signer(
+        &[
+            b"presale_receipt".as_ref(),
+            &[presale_type as u8],
+            &[ctx.bumps.receipt_mint],
+        ],
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.receipt_mint.to_account_info(),
+        &ctx.accounts.receiver_receipt_ata.to_account_info(),
+        &ctx.accounts.receipt_mint.to_account_info(),
+        amount,
+    )?;
+
+    purchase_account.amount -= amount;
+    ctx.accounts.receipt_summary_account.minted_amount += amount;
+    purchase_account.claimed = purchase_account.claimed_amount == purchase_account.amount;
+
+    emit!(TokenizePresalePositionEvent {
+        receiver: ctx.accounts.receiver.key(),
+        presale_type: presale_type as u8,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{enums::*, events::ClaimReferralEvent, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    // Referrer account
+    #[account(
+        init,
+        payer = referrer,
+        space = get_account_size(ReferralAccount::INIT_SPACE),
+        seeds = [
+            b"referral".as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    ctx.accounts.referral_account.referrer = ctx.accounts.referrer.key();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralReward<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    // Platform PDA
+    #[account(
+        seeds = [
+            b"platform".as_ref(),
+        ],
+        bump,
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
+    // Token mint
+    #[account(address = platform.elw_mint)]
+    pub elw_mint: Account<'info, Mint>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    // Referrer account
+    #[account(
+        mut,
+        seeds = [
+            b"referral".as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    /// CHECK: Presale vault
+    #[account(
+        seeds = [
+            b"presale".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Presale),
+    )]
+    pub presale_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Referrer token account
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = elw_mint,
+        associated_token::authority = referrer
+    )]
+    pub referrer_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn claim_referral(ctx: Context<ClaimReferralReward>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let referral_account = &mut ctx.accounts.referral_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    // bonuses are void when buyers are refunded
+    require!(
+        rules.is_soft_cap_reached(ctx.accounts.presale_summary_account.token_sold),
+        CustomError::SoftCapNotReached
+    );
+
+    let claimable_amount = referral_account.earned_amount - referral_account.claimed_amount;
+
+    require!(claimable_amount > 0, CustomError::NoClaimableRewards);
+
+    transfer_token_with_pda_key(
+        "presale",
+        ctx.bumps.presale_vault,
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.presale_token_ata.to_account_info(),
+        &ctx.accounts.referrer_token_ata.to_account_info(),
+        &ctx.accounts.presale_vault.to_account_info(),
+        claimable_amount,
+    )?;
+
+    referral_account.claimed_amount += claimable_amount;
+
+    emit!(ClaimReferralEvent {
+        referrer: ctx.accounts.referrer.key(),
+        amount: claimable_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
+
+use crate::{constants::*, enums::*, events::RefundPresaleEvent, functions::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(presale_type: PresaleType)]
+pub struct RefundPresale<'info> {
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    // USDC mint
+    #[account(address = USDC_MINT)]
+    pub usdc_mint: Account<'info, Mint>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        mut,
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    // Receiver presale account, cancelled by the refund
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"presale".as_ref(),
+            receiver.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_purchase_account: Box<Account<'info, PurchaseAccount>>,
+
+    /// CHECK: Presale escrow vault
+    #[account(
+        mut,
+        seeds = [
+            b"presale_escrow".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = presale_escrow
+    )]
+    pub escrow_usdc_ata: Box<Account<'info, TokenAccount>>,
+
+    // Receiver token account
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = receiver
+    )]
+    pub receiver_usdc_ata: Box<Account<'info, TokenAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn refund(ctx: Context<RefundPresale>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &mut ctx.accounts.presale_summary_account;
+    let purchase_account = &ctx.accounts.receiver_purchase_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    require!(
+        !rules.is_soft_cap_reached(summary_account.token_sold),
+        CustomError::SoftCapReached
+    );
+
+    let sol_amount = purchase_account.sol_paid;
+    let usdc_amount = purchase_account.usdc_paid;
+
+    require!(
+        sol_amount > 0 || usdc_amount > 0,
+        CustomError::NothingToRefund
+    );
+
+    let escrow_bump = ctx.bumps.presale_escrow;
+    let receiver = &ctx.accounts.receiver.to_account_info();
+    let presale_escrow = &ctx.accounts.presale_escrow.to_account_info();
+    let token_program = &ctx.accounts.token_program.to_account_info();
+
+    if sol_amount > 0 {
+        transfer_sol_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            presale_escrow,
+            receiver,
+            sol_amount,
+        )?;
+    }
+
+    if usdc_amount > 0 {
+        transfer_token_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            token_program,
+            &ctx.accounts.escrow_usdc_ata.to_account_info(),
+            &ctx.accounts.receiver_usdc_ata.to_account_info(),
+            presale_escrow,
+            usdc_amount,
+        )?;
+    }
+
+    summary_account.sol_refunded += sol_amount;
+    summary_account.usdc_refunded += usdc_amount;
+
+    emit!(RefundPresaleEvent {
+        receiver: receiver.key(),
+        sol_amount,
+        usdc_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(presale_type: PresaleType)]
+pub struct RefundPresaleToken<'info> {
+    #[account(mut)]
+    pub receiver: Signer<'info>,
+
+    // Payment mint and its registry entry
+    pub payment_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        seeds = [
+            b"payment_mint".as_ref(),
+            payment_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_mint_account: Box<Account<'info, PaymentMintAccount>>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    // Receiver payment account, cancelled by the refund
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [
+            b"presale_payment".as_ref(),
+            receiver.key().as_ref(),
+            payment_mint.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_payment_account: Box<Account<'info, PaymentAccount>>,
+
+    /// CHECK: Receiver presale account, already closed if the SOL and USDC refund ran first
+    #[account(
+        mut,
+        seeds = [
+            b"presale".as_ref(),
+            receiver.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub receiver_purchase_account: UncheckedAccount<'info>,
+
+    /// CHECK: Presale escrow vault
+    #[account(
+        seeds = [
+            b"presale_escrow".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = presale_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    // Receiver token account
+    #[account(
+        init_if_needed,
+        payer = receiver,
+        associated_token::mint = payment_mint,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub receiver_token_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    // Official programs, token or Token-2022 program of the payment mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn refund_token(ctx: Context<RefundPresaleToken>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &ctx.accounts.presale_summary_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    require!(
+        !rules.is_soft_cap_reached(summary_account.token_sold),
+        CustomError::SoftCapReached
+    );
+
+    let token_amount = ctx.accounts.receiver_payment_account.amount;
+
+    require!(token_amount > 0, CustomError::NothingToRefund);
+
+    transfer_token_checked_with_pda_key(
+        "presale_escrow",
+        ctx.bumps.presale_escrow,
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow_token_ata.to_account_info(),
+        &ctx.accounts.payment_mint.to_account_info(),
+        &ctx.accounts.receiver_token_ata.to_account_info(),
+        &ctx.accounts.presale_escrow.to_account_info(),
+        token_amount,
+        ctx.accounts.payment_mint_account.decimals,
+    )?;
+
+    ctx.accounts.payment_mint_account.refunded += token_amount;
+
+    // the refunded part of the position is cancelled, like the SOL and USDC refund
+    let purchase_account = &ctx.accounts.receiver_purchase_account.to_account_info();
+
+    if !purchase_account.data_is_empty() {
+        require_keys_eq!(
+            *purchase_account.owner,
+            crate::ID,
+            CustomError::WrongAccountGiven
+        );
+
+        let mut purchase =
+            PurchaseAccount::try_deserialize(&mut &purchase_account.try_borrow_data()?[..])?;
+        purchase.amount = purchase
+            .amount
+            .saturating_sub(ctx.accounts.receiver_payment_account.token_bought);
+
+        if purchase.amount == 0 && purchase.sol_paid == 0 && purchase.usdc_paid == 0 {
+            close_program_account(purchase_account, &ctx.accounts.receiver.to_account_info())?;
+        } else {
+            purchase.try_serialize(&mut &mut purchase_account.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+    token_interface::{self, TokenInterface},
+};
+
+use crate::{
+    constants::*,
+    enums::*,
+    functions::*,
+    math::{split_bps, Rounding},
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct ReleasePresaleFunds<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // USDC mint
+    #[account(address = USDC_MINT)]
+    pub usdc_mint: Account<'info, Mint>,
+    // WSOl mint
+    #[account(address = WSOL_MINT)]
+    pub wsol_mint: Account<'info, Mint>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        mut,
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    /// CHECK: Presale escrow vault
+    #[account(
+        mut,
+        seeds = [
+            b"presale_escrow".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_escrow: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = presale_escrow
+    )]
+    pub escrow_usdc_ata: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Liquidity vault
+    #[account(
+        seeds = [
+            b"liquidity".as_ref(),
+        ],
+        bump,
+    )]
+    pub liquidity_vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = liquidity_vault
+    )]
+    pub liquidity_usdc_ata: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = liquidity_vault
+    )]
+    pub liquidity_wsol_ata: Box<Account<'info, TokenAccount>>,
+    /// CHECK: EDA vault
+    #[account(
+        mut,
+        seeds = [
+            b"eda".as_ref(),
+        ],
+        bump,
+    )]
+    pub eda_vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = eda_vault
+    )]
+    pub eda_usdc_ata: Box<Account<'info, TokenAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn release(ctx: Context<ReleasePresaleFunds>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &mut ctx.accounts.presale_summary_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    require!(
+        rules.is_soft_cap_reached(summary_account.token_sold),
+        CustomError::SoftCapNotReached
+    );
+
+    require!(
+        !summary_account.is_funds_released,
+        CustomError::PresaleFundsAlreadyReleased
+    );
+
+    let escrow_bump = ctx.bumps.presale_escrow;
+    let eda_vault = &ctx.accounts.eda_vault.to_account_info();
+    let eda_usdc_ata = &ctx.accounts.eda_usdc_ata.to_account_info();
+    let presale_escrow = &ctx.accounts.presale_escrow.to_account_info();
+    let token_program = &ctx.accounts.token_program.to_account_info();
+    let escrow_usdc_ata = &ctx.accounts.escrow_usdc_ata.to_account_info();
+    let liquidity_wsol_ata = &ctx.accounts.liquidity_wsol_ata.to_account_info();
+    let liquidity_usdc_ata = &ctx.accounts.liquidity_usdc_ata.to_account_info();
+
+    // EDA share of the raised funds, the rest goes to liquidity
+    let (sol_eda_amount, sol_liquidity_amount) =
+        split_bps(summary_account.sol_raised, EDA_PERCENTAGE, Rounding::Floor);
+    let (usdc_eda_amount, usdc_liquidity_amount) =
+        split_bps(summary_account.usdc_raised, EDA_PERCENTAGE, Rounding::Floor);
+
+    if summary_account.sol_raised > 0 {
+        transfer_sol_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            presale_escrow,
+            eda_vault,
+            sol_eda_amount,
+        )?;
+
+        transfer_sol_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            presale_escrow,
+            liquidity_wsol_ata,
+            sol_liquidity_amount,
+        )?;
+
+        // Wrap SOL for liquidity pool
+        wrap_sol(token_program, liquidity_wsol_ata)?;
+    }
+
+    if summary_account.usdc_raised > 0 {
+        transfer_token_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            token_program,
+            escrow_usdc_ata,
+            eda_usdc_ata,
+            presale_escrow,
+            usdc_eda_amount,
+        )?;
+
+        transfer_token_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            token_program,
+            escrow_usdc_ata,
+            liquidity_usdc_ata,
+            presale_escrow,
+            usdc_liquidity_amount,
+        )?;
+    }
+
+    summary_account.sol_sent_to_eda = sol_eda_amount;
+    summary_account.usdc_sent_to_eda = usdc_eda_amount;
+    summary_account.sol_sent_to_liquidity = sol_liquidity_amount;
+    summary_account.usdc_sent_to_liquidity = usdc_liquidity_amount;
+    summary_account.is_funds_released = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReleasePresaleTokenFunds<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // Payment mint and its registry entry
+    pub payment_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(
+        mut,
+        seeds = [
+            b"payment_mint".as_ref(),
+            payment_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub payment_mint_account: Box<Account<'info, PaymentMintAccount>>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    /// CHECK: Presale escrow vault
+    #[account(
+        seeds = [
+            b"presale_escrow".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = presale_escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    /// CHECK: EDA vault
+    #[account(
+        seeds = [
+            b"eda".as_ref(),
+        ],
+        bump,
+    )]
+    pub eda_vault: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = payment_mint,
+        associated_token::authority = eda_vault,
+        associated_token::token_program = token_program
+    )]
+    pub eda_token_ata: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+
+    // Official programs, token or Token-2022 program of the payment mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn release_token(ctx: Context<ReleasePresaleTokenFunds>) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+    let summary_account = &ctx.accounts.presale_summary_account;
+    let payment_mint_account = &mut ctx.accounts.payment_mint_account;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    require!(
+        rules.is_soft_cap_reached(summary_account.token_sold),
+        CustomError::SoftCapNotReached
+    );
+
+    require!(
+        !payment_mint_account.is_funds_released,
+        CustomError::PresaleFundsAlreadyReleased
+    );
+
+    let escrow_bump = ctx.bumps.presale_escrow;
+    let presale_escrow = &ctx.accounts.presale_escrow.to_account_info();
+    let token_program = &ctx.accounts.token_program.to_account_info();
+    let escrow_token_ata = &ctx.accounts.escrow_token_ata.to_account_info();
+
+    // no pool is built for a registered mint, so all of it goes to the EDA
+    // where withdraw_eda_token and the spending limit can move it
+    let eda_amount = payment_mint_account.raised;
+
+    if eda_amount > 0 {
+        transfer_token_checked_with_pda_key(
+            "presale_escrow",
+            escrow_bump,
+            token_program,
+            escrow_token_ata,
+            &ctx.accounts.payment_mint.to_account_info(),
+            &ctx.accounts.eda_token_ata.to_account_info(),
+            presale_escrow,
+            eda_amount,
+            payment_mint_account.decimals,
+        )?;
+    }
+
+    payment_mint_account.sent_to_eda = eda_amount;
+    payment_mint_account.sent_to_liquidity = 0;
+    payment_mint_account.is_funds_released = true;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::{enums::*, events::TransferPresalePositionEvent, functions::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(presale_type: PresaleType)]
+pub struct TransferPresalePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: New owner of the position, any wallet or multisig
+    #[account(
+        constraint = new_owner.key() != owner.key() @ CustomError::InvalidReceiver,
+    )]
+    pub new_owner: UncheckedAccount<'info>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Box<Account<'info, PresaleConfigAccount>>,
+
+    // Total sales account
+    #[account(
+        seeds = [
+            b"presale_summary".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_summary_account: Box<Account<'info, SummaryAccount>>,
+
+    // Owner presale account, closed after the transfer
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            b"presale".as_ref(),
+            owner.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub owner_purchase_account: Box<Account<'info, PurchaseAccount>>,
+
+    // New owner presale account, same lockup type
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = get_account_size(PurchaseAccount::INIT_SPACE),
+        seeds = [
+            b"presale".as_ref(),
+            new_owner.key().as_ref(),
+            &[presale_type as u8],
+        ],
+        bump,
+    )]
+    pub new_owner_purchase_account: Box<Account<'info, PurchaseAccount>>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn transfer_position(
+    ctx: Context<TransferPresalePosition>,
+    presale_type: PresaleType,
+) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+
+    // only final positions move, round caps can not be bypassed and no payment is refundable
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+    require!(
+        rules.is_soft_cap_reached(ctx.accounts.presale_summary_account.token_sold),
+        CustomError::SoftCapNotReached
+    );
+
+    let from = &ctx.accounts.owner_purchase_account;
+    let to = &mut ctx.accounts.new_owner_purchase_account;
+
+    require!(from.amount > 0, CustomError::InsufficientBalance);
+    require!(!from.claimed, CustomError::TokensAlreadyClaimed);
+
+    // lockup and unlock time follow the position
+    to.amount += from.amount;
+    to.claimed_amount += from.claimed_amount;
+    to.claimed = to.claimed_amount == to.amount;
+    to.unlock_time = from.unlock_time;
+    to.presale_type = presale_type as u8;
+    for (to_amount, from_amount) in to.round_amounts.iter_mut().zip(from.round_amounts) {
+        *to_amount += from_amount;
+    }
+
+    emit!(TransferPresalePositionEvent {
+        from: ctx.accounts.owner.key(),
+        to: ctx.accounts.new_owner.key(),
+        presale_type: presale_type as u8,
+        amount: from.amount,
+        claimed_amount: from.claimed_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    constants::*,
+    enums::*,
+    events::{ClaimRewardEvent, PublishRewardEpochEvent},
+    functions::*,
+    math::{apply_bps, to_bps, Rounding},
+    state::*,
+};
+
+pub const TOTAL_REWARD: u64 = 500_000_000 * 10u64.pow(9);
+
+#[derive(Accounts)]
+#[instruction(epoch: u32)]
+pub struct PublishRewardEpoch<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.roles.reward_distributor @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Presale config
+    #[account(
+        seeds = [
+            b"presale_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub presale_config: Account<'info, PresaleConfigAccount>,
+
+    // Emission schedule
+    #[account(
+        seeds = [
+            b"emission_schedule".as_ref(),
+        ],
+        bump,
+    )]
+    pub emission_schedule: Box<Account<'info, EmissionScheduleAccount>>,
+
+    // Reward pool
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool".as_ref(),
+            &[reward_pool.id],
+        ],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPoolAccount>,
+
+    // Reward epoch, published once per pool
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(RewardEpochAccount::INIT_SPACE),
+        seeds = [
+            b"reward_epoch".as_ref(),
+            &[reward_pool.id],
+            epoch.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_epoch: Box<Account<'info, RewardEpochAccount>>,
+
+    // Epoch total over all pools
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = get_account_size(RewardEpochTotalAccount::INIT_SPACE),
+        seeds = [
+            b"reward_epoch_total".as_ref(),
+            epoch.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_epoch_total: Box<Account<'info, RewardEpochTotalAccount>>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn publish_epoch(
+    ctx: Context<PublishRewardEpoch>,
+    epoch: u32,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    leaf_count: u32,
+) -> Result<()> {
+    let rules = &ctx.accounts.presale_config.rules;
+
+    require!(rules.is_presale_ended(), CustomError::PresaleIsNotEnded);
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+
+    require!(reward_pool.is_enabled, CustomError::RewardPoolIsDisabled);
+
+    // epochs are the months after the presale, capped by the pool share of the month emission
+    let epoch_start_time = get_months_later(rules.end_time, epoch);
+    let emission = ctx
+        .accounts
+        .emission_schedule
+        .schedule
+        .get_emission(rules.end_time, epoch_start_time);
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        epoch_start_time <= current_time,
+        CustomError::ClaimableRewardNotReady
+    );
+    require!(
+        merkle_root != [0u8; 32]
+            && leaf_count > 0
+            && leaf_count <= MAX_REWARD_EPOCH_RECEIVERS
+            && epoch < MAX_REWARD_EPOCHS
+            && total_amount <= apply_bps(emission, reward_pool.share_percentage, Rounding::Floor),
+        CustomError::InvalidRewardEpoch
+    );
+
+    // shares can move between pools, the month emission is never exceeded over all of them
+    let reward_epoch_total = &mut ctx.accounts.reward_epoch_total;
+
+    require!(
+        reward_epoch_total.published_amount + total_amount <= emission,
+        CustomError::InvalidRewardEpoch
+    );
+
+    reward_epoch_total.epoch = epoch;
+    reward_epoch_total.published_amount += total_amount;
+    reward_pool.published_amount += total_amount;
+
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+    reward_epoch.pool = reward_pool.id;
+    reward_epoch.epoch = epoch;
+    reward_epoch.merkle_root = merkle_root;
+    reward_epoch.total_amount = total_amount;
+    reward_epoch.claimed_amount = 0;
+    reward_epoch.leaf_count = leaf_count;
+    reward_epoch.claimed_bitmap = vec![0u8; leaf_count.div_ceil(8) as usize];
+
+    emit!(PublishRewardEpochEvent {
+        pool: reward_pool.id,
+        epoch,
+        merkle_root,
+        total_amount,
+        leaf_count,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Reward receiver in the epoch tree, any wallet or PDA
+    pub receiver: UncheckedAccount<'info>,
+
+    // Platform PDA
+    #[account(
+        seeds = [
+            b"platform".as_ref(),
+        ],
+        bump,
+    )]
+    pub platform: Account<'info, PlatformAccount>,
+
+    // Reward epoch
+    #[account(
+        mut,
+        seeds = [
+            b"reward_epoch".as_ref(),
+            &[reward_epoch.pool],
+            reward_epoch.epoch.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_epoch: Box<Account<'info, RewardEpochAccount>>,
+
+    // Reward pool of the epoch
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool".as_ref(),
+            &[reward_epoch.pool],
+        ],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPoolAccount>,
+
+    /// CHECK: Reward vault
+    #[account(
+        seeds = [
+            b"reward".as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Reward),
+    )]
+    pub reward_token_ata: Account<'info, TokenAccount>,
+
+    // Token mint
+    #[account(address = platform.elw_mint)]
+    pub elw_mint: Account<'info, Mint>,
+
+    // Receiver
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = get_account_size(RewardAccount::INIT_SPACE),
+        seeds = [
+            b"reward".as_ref(),
+            receiver.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = get_account_size(UserRewardAccount::INIT_SPACE),
+        seeds = [
+            b"user_reward".as_ref(),
+            receiver.key().as_ref(),
+            &[reward_epoch.pool],
+        ],
+        bump,
+    )]
+    pub user_reward_account: Box<Account<'info, UserRewardAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = elw_mint,
+        associated_token::authority = receiver
+    )]
+    pub receiver_token_ata: Account<'info, TokenAccount>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn claim(
+    ctx: Context<ClaimReward>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let receiver = ctx.accounts.receiver.key();
+    let reward_epoch = &mut ctx.accounts.reward_epoch;
+
+    require!(
+        index < reward_epoch.leaf_count,
+        CustomError::InvalidMerkleProof
+    );
+    require!(
+        !reward_epoch.is_claimed(index),
+        CustomError::AlreadyClaimedForThisPeriod
+    );
+    require!(
+        reward_epoch.verify_claim(index, &receiver, amount, &proof),
+        CustomError::InvalidMerkleProof
+    );
+
+    // one claim per pool epoch and receiver, even if the tree lists the receiver twice
+    let user_reward_account = &mut ctx.accounts.user_reward_account;
+
+    require!(
+        !user_reward_account.is_claimed(reward_epoch.epoch),
+        CustomError::AlreadyClaimedForThisPeriod
+    );
+    require!(
+        reward_epoch.claimed_amount + amount <= reward_epoch.total_amount,
+        CustomError::InsufficientReward
+    );
+
+    let reward_token_ata = &ctx.accounts.reward_token_ata;
+
+    require!(
+        reward_token_ata.amount >= amount,
+        CustomError::AllRewardsClaimed
+    );
+
+    transfer_token_with_pda_key(
+        "reward",
+        ctx.bumps.reward_vault,
+        &ctx.accounts.token_program.to_account_info(),
+        &reward_token_ata.to_account_info(),
+        &ctx.accounts.receiver_token_ata.to_account_info(),
+        &ctx.accounts.reward_vault.to_account_info(),
+        amount,
+    )?;
+
+    reward_epoch.set_claimed(index);
+    reward_epoch.claimed_amount += amount;
+
+    user_reward_account.receiver = receiver;
+    user_reward_account.pool = reward_epoch.pool;
+    user_reward_account.set_claimed(reward_epoch.epoch);
+    user_reward_account.claimed_amount += amount;
+    user_reward_account.last_claim_time = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.reward_pool.claimed_amount += amount;
+
+    let reward_account = &mut ctx.accounts.reward_account;
+    reward_account.amount += amount;
+
+    // Share of the total prize received so far, in basis points
+    reward_account.percentage = to_bps(reward_account.amount, TOTAL_REWARD, Rounding::Nearest);
+
+    emit!(ClaimRewardEvent {
+        receiver,
+        amount,
+        pool: reward_epoch.pool,
+        epoch: reward_epoch.epoch,
+    });
+
+    Ok(())
+}
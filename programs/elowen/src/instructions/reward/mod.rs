@@ -0,0 +1,5 @@
+pub mod epoch;
+pub mod pool;
+
+pub use epoch::*;
+pub use pool::*;
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, enums::*, functions::*, math::BASIS_POINTS, state::*};
+
+#[derive(Accounts)]
+#[instruction(id: u8)]
+pub struct CreateRewardPool<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Reward pool
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(RewardPoolAccount::INIT_SPACE),
+        seeds = [
+            b"reward_pool".as_ref(),
+            &[id],
+        ],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPoolAccount>,
+
+    // Reward pools shares
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = get_account_size(RewardPoolsAccount::INIT_SPACE),
+        seeds = [
+            b"reward_pools".as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_pools: Account<'info, RewardPoolsAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_pool(ctx: Context<CreateRewardPool>, id: u8, share_percentage: u16) -> Result<()> {
+    let reward_pools = &mut ctx.accounts.reward_pools;
+
+    require!(
+        id < MAX_REWARD_POOLS
+            && reward_pools.total_share_percentage as u64 + share_percentage as u64 <= BASIS_POINTS,
+        CustomError::InvalidRewardPool
+    );
+
+    reward_pools.total_share_percentage += share_percentage;
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.id = id;
+    reward_pool.share_percentage = share_percentage;
+    reward_pool.is_enabled = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateRewardPool<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Reward pool
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pool".as_ref(),
+            &[reward_pool.id],
+        ],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPoolAccount>,
+
+    // Reward pools shares
+    #[account(
+        mut,
+        seeds = [
+            b"reward_pools".as_ref(),
+        ],
+        bump,
+    )]
+    pub reward_pools: Account<'info, RewardPoolsAccount>,
+}
+
+pub fn update_pool(
+    ctx: Context<UpdateRewardPool>,
+    share_percentage: u16,
+    is_enabled: bool,
+) -> Result<()> {
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    let reward_pools = &mut ctx.accounts.reward_pools;
+
+    // only enabled pools hold a share of the emission
+    let mut total_share_percentage = reward_pools.total_share_percentage as u64;
+    if reward_pool.is_enabled {
+        total_share_percentage -= reward_pool.share_percentage as u64;
+    }
+    if is_enabled {
+        total_share_percentage += share_percentage as u64;
+    }
+
+    require!(
+        total_share_percentage <= BASIS_POINTS,
+        CustomError::InvalidRewardPool
+    );
+
+    reward_pools.total_share_percentage = total_share_percentage as u16;
+    reward_pool.share_percentage = share_percentage;
+    reward_pool.is_enabled = is_enabled;
+
+    Ok(())
+}
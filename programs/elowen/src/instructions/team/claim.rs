@@ -4,7 +4,7 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{constants::*, enums::*, functions::*, state::*};
+use crate::{enums::*, functions::*, state::*};
 
 #[derive(Accounts)]
 pub struct ClaimTeamELW<'info> {
@@ -14,6 +14,16 @@ pub struct ClaimTeamELW<'info> {
     // Token mint
     pub elw_mint: Account<'info, Mint>,
 
+    // Member vesting
+    #[account(
+        seeds = [
+            b"team_vesting".as_ref(),
+            member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub team_vesting: Account<'info, TeamVestingAccount>,
+
     /// CHECK: Team vault
     #[account(
         seeds = [
@@ -56,47 +66,19 @@ pub struct ClaimTeamELW<'info> {
 
 pub fn claim(ctx: Context<ClaimTeamELW>) -> Result<()> {
     let member = &ctx.accounts.member;
-
-    require!(is_team_member(&member.key()), CustomError::Unauthorized);
-
     let team_token_ata = &ctx.accounts.team_token_ata;
+    let team_vesting = &ctx.accounts.team_vesting;
     let member_claim = &mut ctx.accounts.member_claim;
 
-    let unlock_periods = [
-        get_months_later(PRESALE_RULES.end_time, 3),
-        get_months_later(PRESALE_RULES.end_time, 6),
-        get_months_later(PRESALE_RULES.end_time, 9),
-        get_months_later(PRESALE_RULES.end_time, 12),
-    ];
-
-    let percentage = get_member_percentage(&member.key());
-
-    require!(percentage.is_some(), CustomError::MemberShareNotFound);
-
-    let total = calculate_by_percentage(SUPPLY, TEAM_PERCENTAGE);
-    let member_total = calculate_by_percentage(total, percentage.unwrap());
-    let total_amount_for_period = member_total / unlock_periods.len() as u64;
-
     let current_time = Clock::get()?.unix_timestamp;
+    let vested_amount = team_vesting.get_vested_amount(current_time);
 
-    let current_period = unlock_periods
-        .iter()
-        .rev()
-        .find(|period| current_time >= **period);
-
-    require!(current_period.is_some(), CustomError::PeriodNotReached);
-
-    require!(
-        member_claim.last_period != *current_period.unwrap(),
-        CustomError::AlreadyClaimedForThisPeriod
-    );
+    require!(vested_amount > 0, CustomError::PeriodNotReached);
 
-    let period_count = unlock_periods
-        .iter()
-        .filter(|&&period| period > member_claim.last_period && period <= *current_period.unwrap())
-        .count();
+    // the last claim after the end time takes the rounding remainder
+    let transfer_amount = vested_amount.saturating_sub(member_claim.amount);
 
-    let transfer_amount = total_amount_for_period * period_count as u64;
+    require!(transfer_amount > 0, CustomError::NoClaimableRewards);
 
     require!(
         team_token_ata.amount >= transfer_amount,
@@ -119,9 +101,10 @@ pub fn claim(ctx: Context<ClaimTeamELW>) -> Result<()> {
     )?;
 
     member_claim.amount += transfer_amount;
-    member_claim.last_period = *current_period.unwrap();
+    // last claim time
+    member_claim.last_period = current_time;
 
-    if ctx.accounts.team_token_ata.amount == 0 {
+    if reload_token_account_by_info(sender_account)?.amount == 0 {
         close_token_account_with_pda_key(
             "team",
             ctx.bumps.team_vault,
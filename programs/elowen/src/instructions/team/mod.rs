@@ -0,0 +1,5 @@
+pub mod claim;
+pub mod vesting;
+
+pub use claim::*;
+pub use vesting::*;
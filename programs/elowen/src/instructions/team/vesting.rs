@@ -0,0 +1,307 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    constants::*,
+    enums::*,
+    functions::*,
+    math::{apply_bps, Rounding},
+    state::*,
+};
+
+#[derive(Accounts)]
+pub struct CreateTeamVesting<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    /// CHECK: Team member wallet
+    pub member: UncheckedAccount<'info>,
+
+    // Member vesting, a revoked one can be replaced
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = get_account_size(TeamVestingAccount::INIT_SPACE),
+        seeds = [
+            b"team_vesting".as_ref(),
+            member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub team_vesting: Account<'info, TeamVestingAccount>,
+
+    // Member claims, only exists once the member claimed
+    #[account(
+        mut,
+        seeds = [
+            b"member".as_ref(),
+            member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub member_claim: Option<Account<'info, MemberClaimAccount>>,
+
+    // Team allocation tracker
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = get_account_size(TeamStateAccount::INIT_SPACE),
+        seeds = [
+            b"team_state".as_ref(),
+        ],
+        bump,
+    )]
+    pub team_state: Account<'info, TeamStateAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_vesting(
+    ctx: Context<CreateTeamVesting>,
+    total_amount: u64,
+    schedule: VestingSchedule,
+) -> Result<()> {
+    require!(total_amount > 0, CustomError::InvalidVestingSchedule);
+    schedule.validate()?;
+
+    let team_vesting = &ctx.accounts.team_vesting;
+
+    // claims restart with the new vesting, so the revoked one must be fully claimed
+    if team_vesting.member != Pubkey::default() {
+        require!(team_vesting.is_revoked, CustomError::VestingIsNotRevoked);
+
+        let claimed_amount = ctx
+            .accounts
+            .member_claim
+            .as_ref()
+            .map_or(0, |member_claim| member_claim.amount);
+        require!(
+            claimed_amount >= team_vesting.total_amount,
+            CustomError::VestedTokensNotClaimed
+        );
+
+        if let Some(member_claim) = &mut ctx.accounts.member_claim {
+            member_claim.amount = 0;
+        }
+    }
+
+    let team_state = &mut ctx.accounts.team_state;
+
+    require!(
+        team_state.allocated_amount + total_amount
+            <= apply_bps(SUPPLY, TEAM_PERCENTAGE, Rounding::Floor),
+        CustomError::ExceedsTheTeamAllocation
+    );
+
+    team_state.allocated_amount += total_amount;
+
+    let team_vesting = &mut ctx.accounts.team_vesting;
+    team_vesting.member = ctx.accounts.member.key();
+    team_vesting.total_amount = total_amount;
+    team_vesting.schedule = schedule;
+    team_vesting.is_revoked = false;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevokeTeamVesting<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Token mint
+    pub elw_mint: Account<'info, Mint>,
+
+    // Member vesting
+    #[account(
+        mut,
+        seeds = [
+            b"team_vesting".as_ref(),
+            team_vesting.member.as_ref(),
+        ],
+        bump,
+    )]
+    pub team_vesting: Account<'info, TeamVestingAccount>,
+
+    /// CHECK: Team vault
+    #[account(
+        seeds = [
+            b"team".as_ref(),
+        ],
+        bump,
+    )]
+    pub team_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Team),
+    )]
+    pub team_token_ata: Account<'info, TokenAccount>,
+
+    // EDA token account, receives the unvested tokens
+    #[account(
+        mut,
+        address = get_vault_account_token_ata(elw_mint.key(), VaultAccount::Eda),
+    )]
+    pub eda_token_ata: Account<'info, TokenAccount>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn revoke_vesting(ctx: Context<RevokeTeamVesting>) -> Result<()> {
+    let team_vesting = &mut ctx.accounts.team_vesting;
+
+    require!(!team_vesting.is_revoked, CustomError::VestingIsRevoked);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let vested_amount = team_vesting.get_vested_amount(current_time);
+    let unvested_amount = team_vesting.total_amount - vested_amount;
+
+    if unvested_amount > 0 {
+        transfer_token_with_pda_key(
+            "team",
+            ctx.bumps.team_vault,
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.team_token_ata.to_account_info(),
+            &ctx.accounts.eda_token_ata.to_account_info(),
+            &ctx.accounts.team_vault.to_account_info(),
+            unvested_amount,
+        )?;
+    }
+
+    // vested tokens stay claimable by the member, the unvested ones left the team vault
+    // so they stay counted against the team allocation
+    team_vesting.total_amount = vested_amount;
+    team_vesting.is_revoked = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RotateTeamMember<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    /// CHECK: Current team member wallet, compromised or lost
+    pub member: UncheckedAccount<'info>,
+    /// CHECK: New team member wallet
+    #[account(
+        constraint = new_member.key() != member.key() @ CustomError::InvalidReceiver,
+    )]
+    pub new_member: UncheckedAccount<'info>,
+
+    // Member vesting, moved to the new wallet
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"team_vesting".as_ref(),
+            member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub team_vesting: Account<'info, TeamVestingAccount>,
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(TeamVestingAccount::INIT_SPACE),
+        seeds = [
+            b"team_vesting".as_ref(),
+            new_member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub new_team_vesting: Account<'info, TeamVestingAccount>,
+
+    /// CHECK: Member claim progress, empty if the member never claimed, checked in the instruction
+    #[account(
+        mut,
+        seeds = [
+            b"member".as_ref(),
+            member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub member_claim: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(MemberClaimAccount::INIT_SPACE),
+        seeds = [
+            b"member".as_ref(),
+            new_member.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub new_member_claim: Account<'info, MemberClaimAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn rotate_member(ctx: Context<RotateTeamMember>) -> Result<()> {
+    let team_vesting = &ctx.accounts.team_vesting;
+    let new_team_vesting = &mut ctx.accounts.new_team_vesting;
+
+    new_team_vesting.member = ctx.accounts.new_member.key();
+    new_team_vesting.total_amount = team_vesting.total_amount;
+    new_team_vesting.schedule = team_vesting.schedule.clone();
+    new_team_vesting.is_revoked = team_vesting.is_revoked;
+
+    // the claim PDA is always passed, so claimed tokens can not be claimed again by the new wallet
+    let member_claim = &ctx.accounts.member_claim.to_account_info();
+
+    if !member_claim.data_is_empty() {
+        require_keys_eq!(
+            *member_claim.owner,
+            crate::ID,
+            CustomError::WrongAccountGiven
+        );
+
+        let claim = MemberClaimAccount::try_deserialize(&mut &member_claim.try_borrow_data()?[..])?;
+
+        let new_member_claim = &mut ctx.accounts.new_member_claim;
+        new_member_claim.amount = claim.amount;
+        new_member_claim.last_period = claim.last_period;
+
+        close_program_account(member_claim, &ctx.accounts.signer.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{enums::*, events::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct ClaimVesting<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    // Vested token mint
+    #[account(address = vesting_account.mint)]
+    pub mint: Account<'info, Mint>,
+
+    // Vesting account
+    #[account(
+        mut,
+        seeds = [
+            b"vesting".as_ref(),
+            beneficiary.key().as_ref(),
+            vesting_account.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub vesting_account: Box<Account<'info, VestingAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_account
+    )]
+    pub vesting_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Beneficiary token account
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary
+    )]
+    pub beneficiary_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn claim(ctx: Context<ClaimVesting>) -> Result<()> {
+    let vesting_account = &mut ctx.accounts.vesting_account;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let claimable_amount = vesting_account.get_claimable_amount(current_time);
+
+    require!(claimable_amount > 0, CustomError::NoClaimableRewards);
+
+    let beneficiary = ctx.accounts.beneficiary.key();
+    let id = vesting_account.id.to_le_bytes();
+
+    transfer_token_with_signer(
+        &[
+            b"vesting".as_ref(),
+            beneficiary.as_ref(),
+            id.as_ref(),
+            &[ctx.bumps.vesting_account],
+        ],
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.vesting_token_ata.to_account_info(),
+        &ctx.accounts.beneficiary_token_ata.to_account_info(),
+        &vesting_account.to_account_info(),
+        claimable_amount,
+    )?;
+
+    vesting_account.claimed_amount += claimable_amount;
+
+    emit!(ClaimVestingEvent {
+        beneficiary,
+        id: vesting_account.id,
+        amount: claimable_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{constants::*, enums::*, events::*, functions::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(id: u64, vault: VaultAccount)]
+pub struct CreateVesting<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    /// CHECK: Vesting beneficiary wallet
+    pub beneficiary: UncheckedAccount<'info>,
+
+    // Vested token mint
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: Vault funding the vesting
+    #[account(
+        address = get_vault_account(vault).0,
+    )]
+    pub source_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = source_vault
+    )]
+    pub source_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Vault spending limit
+    #[account(
+        mut,
+        seeds = [
+            b"spending_limit".as_ref(),
+            vault.as_str().as_bytes(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Box<Account<'info, SpendingLimitAccount>>,
+
+    // Vesting account, holds the tokens until claimed
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(VestingAccount::INIT_SPACE),
+        seeds = [
+            b"vesting".as_ref(),
+            beneficiary.key().as_ref(),
+            id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub vesting_account: Box<Account<'info, VestingAccount>>,
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_account
+    )]
+    pub vesting_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn create(
+    ctx: Context<CreateVesting>,
+    id: u64,
+    vault: VaultAccount,
+    total_amount: u64,
+    schedule: VestingSchedule,
+    is_revocable: bool,
+    milestones: Vec<GrantMilestone>,
+) -> Result<()> {
+    // only the EDA and treasury fund vestings, through their spending limits. The team
+    // vault would bypass the team allocation cap and the presale vault the buyers' claims
+    require!(vault.is_spending_limited(), CustomError::WrongAccountGiven);
+
+    require!(total_amount > 0, CustomError::InvalidVestingSchedule);
+    schedule.validate()?;
+
+    // milestones of a grant split the whole amount
+    if !milestones.is_empty() {
+        let milestones_amount = milestones
+            .iter()
+            .try_fold(0u64, |total, milestone| total.checked_add(milestone.amount));
+
+        require!(
+            milestones.len() <= MAX_GRANT_MILESTONES
+                && milestones.iter().all(|milestone| milestone.amount > 0)
+                && milestones_amount == Some(total_amount),
+            CustomError::InvalidGrantMilestones
+        );
+    }
+
+    require!(
+        ctx.accounts.source_token_ata.amount >= total_amount,
+        CustomError::NotEnoughBalanceInVault
+    );
+
+    // funding a vesting moves the vault funds like a withdrawal
+    ctx.accounts
+        .spending_limit
+        .spend(total_amount, Clock::get()?.unix_timestamp)?;
+
+    transfer_token_with_pda_key(
+        vault.as_str(),
+        get_vault_account(vault).1,
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.source_token_ata.to_account_info(),
+        &ctx.accounts.vesting_token_ata.to_account_info(),
+        &ctx.accounts.source_vault.to_account_info(),
+        total_amount,
+    )?;
+
+    let vesting_account = &mut ctx.accounts.vesting_account;
+    vesting_account.id = id;
+    vesting_account.beneficiary = ctx.accounts.beneficiary.key();
+    vesting_account.mint = ctx.accounts.mint.key();
+    vesting_account.vault = vault;
+    vesting_account.funder = ctx.accounts.source_token_ata.key();
+    vesting_account.total_amount = total_amount;
+    vesting_account.claimed_amount = 0;
+    vesting_account.schedule = schedule;
+    // milestones start unapproved
+    vesting_account.milestones = milestones
+        .into_iter()
+        .map(|milestone| GrantMilestone {
+            is_approved: false,
+            ..milestone
+        })
+        .collect();
+    vesting_account.is_revocable = is_revocable;
+    vesting_account.is_revoked = false;
+
+    emit!(CreateVestingEvent {
+        beneficiary: vesting_account.beneficiary,
+        id,
+        vault,
+        mint: vesting_account.mint,
+        total_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{enums::*, events::*, state::*};
+
+#[derive(Accounts)]
+pub struct ApproveVestingMilestone<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Vesting account
+    #[account(
+        mut,
+        seeds = [
+            b"vesting".as_ref(),
+            vesting_account.beneficiary.as_ref(),
+            vesting_account.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub vesting_account: Box<Account<'info, VestingAccount>>,
+}
+
+pub fn approve_milestone(ctx: Context<ApproveVestingMilestone>, index: u8) -> Result<()> {
+    let vesting_account = &mut ctx.accounts.vesting_account;
+
+    require!(!vesting_account.is_revoked, CustomError::VestingIsRevoked);
+
+    let milestone = vesting_account
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(CustomError::InvalidGrantMilestones)?;
+
+    require!(
+        !milestone.is_approved,
+        CustomError::MilestoneAlreadyApproved
+    );
+
+    milestone.is_approved = true;
+    let amount = milestone.amount;
+
+    emit!(ApproveVestingMilestoneEvent {
+        beneficiary: vesting_account.beneficiary,
+        id: vesting_account.id,
+        index,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod claim;
+pub mod create;
+pub mod milestone;
+pub mod revoke;
+
+pub use claim::*;
+pub use create::*;
+pub use milestone::*;
+pub use revoke::*;
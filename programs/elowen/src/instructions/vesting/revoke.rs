@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{enums::*, events::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct RevokeVesting<'info> {
+    #[account(
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Vested token mint
+    #[account(address = vesting_account.mint)]
+    pub mint: Account<'info, Mint>,
+
+    // Vesting account
+    #[account(
+        mut,
+        seeds = [
+            b"vesting".as_ref(),
+            vesting_account.beneficiary.as_ref(),
+            vesting_account.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub vesting_account: Box<Account<'info, VestingAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting_account
+    )]
+    pub vesting_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Funder token account, receives the unvested tokens
+    #[account(
+        mut,
+        address = vesting_account.funder,
+    )]
+    pub funder_token_ata: Box<Account<'info, TokenAccount>>,
+
+    // Funder vault spending limit, credited with the unvested tokens
+    #[account(
+        mut,
+        seeds = [
+            b"spending_limit".as_ref(),
+            vesting_account.vault.as_str().as_bytes(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Box<Account<'info, SpendingLimitAccount>>,
+
+    // Official programs
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn revoke(ctx: Context<RevokeVesting>) -> Result<()> {
+    let vesting_account = &mut ctx.accounts.vesting_account;
+
+    require!(
+        vesting_account.is_revocable,
+        CustomError::VestingIsNotRevocable
+    );
+    require!(!vesting_account.is_revoked, CustomError::VestingIsRevoked);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let vested_amount = vesting_account.get_vested_amount(current_time);
+    let unvested_amount = vesting_account.total_amount - vested_amount;
+
+    if unvested_amount > 0 {
+        let beneficiary = vesting_account.beneficiary;
+        let id = vesting_account.id.to_le_bytes();
+
+        transfer_token_with_signer(
+            &[
+                b"vesting".as_ref(),
+                beneficiary.as_ref(),
+                id.as_ref(),
+                &[ctx.bumps.vesting_account],
+            ],
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.vesting_token_ata.to_account_info(),
+            &ctx.accounts.funder_token_ata.to_account_info(),
+            &vesting_account.to_account_info(),
+            unvested_amount,
+        )?;
+
+        // returned tokens are no longer spent from the vault
+        ctx.accounts
+            .spending_limit
+            .restore(unvested_amount, current_time);
+    }
+
+    // vested tokens stay claimable by the beneficiary
+    vesting_account.total_amount = vested_amount;
+    vesting_account.is_revoked = true;
+
+    emit!(RevokeVestingEvent {
+        beneficiary: vesting_account.beneficiary,
+        id: vesting_account.id,
+        returned_amount: unvested_amount,
+    });
+
+    Ok(())
+}
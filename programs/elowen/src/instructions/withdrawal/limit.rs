@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{enums::*, functions::*, state::*};
+
+#[derive(Accounts)]
+#[instruction(vault: VaultAccount, mint: Pubkey)]
+pub struct SetSpendingLimit<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Vault spending limit
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = get_account_size(SpendingLimitAccount::INIT_SPACE),
+        seeds = [
+            b"spending_limit".as_ref(),
+            vault.as_str().as_bytes(),
+            mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_limit(
+    ctx: Context<SetSpendingLimit>,
+    vault: VaultAccount,
+    mint: Pubkey,
+    limit_amount: u64,
+    period: i64,
+    timelock: i64,
+) -> Result<()> {
+    // only the multisig withdrawals are limited
+    require!(vault.is_spending_limited(), CustomError::WrongAccountGiven);
+    require!(
+        period > 0 && timelock >= 0,
+        CustomError::InvalidSpendingLimit
+    );
+
+    let spending_limit = &mut ctx.accounts.spending_limit;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    spending_limit.apply_pending_limit(current_time);
+
+    // a new limit and a tighter one apply now, a looser one waits for the current timelock
+    let is_new = spending_limit.mint == Pubkey::default();
+    let is_tighter = limit_amount <= spending_limit.limit_amount
+        && period >= spending_limit.period
+        && timelock >= spending_limit.timelock;
+
+    if is_new || is_tighter {
+        spending_limit.vault = vault;
+        spending_limit.mint = mint;
+        spending_limit.limit_amount = limit_amount;
+        spending_limit.period = period;
+        spending_limit.timelock = timelock;
+        spending_limit.pending_limit = None;
+    } else {
+        spending_limit.pending_limit = Some(PendingSpendingLimit {
+            limit_amount,
+            period,
+            timelock,
+            effective_after: current_time + spending_limit.timelock,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod limit;
+pub mod request;
+
+pub use limit::*;
+pub use request::*;
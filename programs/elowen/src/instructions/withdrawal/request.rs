@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{constants::*, enums::*, events::*, functions::*, state::*};
+
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Receiver
+    pub receiver: SystemAccount<'info>,
+
+    // Vault spending limit
+    #[account(
+        mut,
+        constraint = spending_limit.vault.is_spending_limited() @ CustomError::WrongAccountGiven,
+        seeds = [
+            b"spending_limit".as_ref(),
+            spending_limit.vault.as_str().as_bytes(),
+            spending_limit.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
+
+    // Queued withdrawal
+    #[account(
+        init,
+        payer = signer,
+        space = get_account_size(WithdrawalRequestAccount::INIT_SPACE),
+        seeds = [
+            b"withdrawal_request".as_ref(),
+            spending_limit.key().as_ref(),
+            spending_limit.request_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequestAccount>,
+
+    // Official programs
+    pub system_program: Program<'info, System>,
+}
+
+pub fn queue(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+    let spending_limit = &mut ctx.accounts.spending_limit;
+    let withdrawal_request = &mut ctx.accounts.withdrawal_request;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    spending_limit.apply_pending_limit(current_time);
+
+    // only withdrawals above what is left of the limit are queued
+    require!(
+        amount > 0
+            && spending_limit
+                .get_spent_amount(current_time)
+                .saturating_add(amount)
+                > spending_limit.limit_amount,
+        CustomError::InvalidWithdrawalAmount
+    );
+
+    withdrawal_request.id = spending_limit.request_count;
+    withdrawal_request.spending_limit = spending_limit.key();
+    withdrawal_request.receiver = ctx.accounts.receiver.key();
+    withdrawal_request.amount = amount;
+    withdrawal_request.execute_after = current_time + spending_limit.timelock;
+
+    spending_limit.request_count += 1;
+
+    emit!(QueueWithdrawalEvent {
+        spending_limit: withdrawal_request.spending_limit,
+        id: withdrawal_request.id,
+        receiver: withdrawal_request.receiver,
+        amount,
+        execute_after: withdrawal_request.execute_after,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Vault spending limit
+    #[account(
+        constraint = spending_limit.vault.is_spending_limited() @ CustomError::WrongAccountGiven,
+        seeds = [
+            b"spending_limit".as_ref(),
+            spending_limit.vault.as_str().as_bytes(),
+            spending_limit.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimitAccount>,
+
+    // Queued withdrawal, closed after the execution
+    #[account(
+        mut,
+        close = signer,
+        has_one = receiver @ CustomError::InvalidReceiver,
+        seeds = [
+            b"withdrawal_request".as_ref(),
+            spending_limit.key().as_ref(),
+            withdrawal_request.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequestAccount>,
+
+    /// CHECK: Vault of the spending limit
+    #[account(
+        mut,
+        address = get_vault_account(spending_limit.vault).0,
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    // Receiver
+    #[account(mut)]
+    pub receiver: SystemAccount<'info>,
+
+    // Token accounts, not needed for SOL withdrawals
+    #[account(
+        address = spending_limit.mint,
+        mint::token_program = token_program,
+    )]
+    pub mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = receiver,
+        associated_token::token_program = token_program
+    )]
+    pub receiver_token_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Official programs, token or Token-2022 program of the mint
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn execute(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let spending_limit = &ctx.accounts.spending_limit;
+    let withdrawal_request = &ctx.accounts.withdrawal_request;
+
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        current_time >= withdrawal_request.execute_after,
+        CustomError::TimelockNotElapsed
+    );
+
+    let amount = withdrawal_request.amount;
+    let vault_key = spending_limit.vault.as_str();
+    let (_, vault_bump) = get_vault_account(spending_limit.vault);
+
+    if spending_limit.mint == WSOL_MINT {
+        require!(
+            ctx.accounts.vault.lamports() >= amount,
+            CustomError::NotEnoughBalanceInVault
+        );
+
+        transfer_sol_with_pda_key(
+            vault_key,
+            vault_bump,
+            &ctx.accounts.vault,
+            &ctx.accounts.receiver,
+            amount,
+        )?;
+    } else {
+        let (mint, vault_token_ata, receiver_token_ata) = match (
+            &ctx.accounts.mint,
+            &ctx.accounts.vault_token_ata,
+            &ctx.accounts.receiver_token_ata,
+        ) {
+            (Some(mint), Some(vault_token_ata), Some(receiver_token_ata)) => {
+                (mint, vault_token_ata, receiver_token_ata)
+            }
+            _ => return err!(CustomError::WrongAccountGiven),
+        };
+
+        require!(
+            vault_token_ata.amount >= amount,
+            CustomError::NotEnoughBalanceInVault
+        );
+
+        transfer_token_checked_with_pda_key(
+            vault_key,
+            vault_bump,
+            &ctx.accounts.token_program.to_account_info(),
+            &vault_token_ata.to_account_info(),
+            &mint.to_account_info(),
+            &receiver_token_ata.to_account_info(),
+            &ctx.accounts.vault.to_account_info(),
+            amount,
+            mint.decimals,
+        )?;
+    }
+
+    emit!(ExecuteWithdrawalEvent {
+        spending_limit: spending_limit.key(),
+        id: withdrawal_request.id,
+        receiver: withdrawal_request.receiver,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelWithdrawal<'info> {
+    #[account(
+        mut,
+        constraint = signer.key() == admin_config.owner
+            || signer.key() == admin_config.roles.treasurer @ CustomError::Unauthorized,
+    )]
+    pub signer: Signer<'info>,
+
+    // Admin roles
+    #[account(
+        seeds = [
+            b"admin_config".as_ref(),
+        ],
+        bump,
+    )]
+    pub admin_config: Account<'info, AdminConfigAccount>,
+
+    // Queued withdrawal
+    #[account(
+        mut,
+        close = signer,
+        seeds = [
+            b"withdrawal_request".as_ref(),
+            withdrawal_request.spending_limit.as_ref(),
+            withdrawal_request.id.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub withdrawal_request: Account<'info, WithdrawalRequestAccount>,
+}
+
+pub fn cancel(ctx: Context<CancelWithdrawal>) -> Result<()> {
+    let withdrawal_request = &ctx.accounts.withdrawal_request;
+
+    emit!(CancelWithdrawalEvent {
+        spending_limit: withdrawal_request.spending_limit,
+        id: withdrawal_request.id,
+        amount: withdrawal_request.amount,
+    });
+
+    Ok(())
+}
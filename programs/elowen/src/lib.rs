@@ -9,20 +9,26 @@ mod enums;
 mod events;
 mod functions;
 mod instructions;
+mod math;
 mod state;
 
 use enums::*;
 use instructions::{
+    admin::{self, *},
     alt::{self, *},
     eda::{self, *},
     elw::{self, *},
+    emission::{self, *},
     liquidity::{self, *},
     platform::{self, *},
     premium::{self, *},
     presale::{self, *},
     reward::{self, *},
     team::{self, *},
+    vesting::{self, *},
+    withdrawal::{self, *},
 };
+use state::{AdminRoles, EmissionSchedule, GrantMilestone, PresaleRules, VestingSchedule};
 
 #[cfg(not(feature = "no-entrypoint"))]
 solana_security_txt::security_txt! {
@@ -43,6 +49,30 @@ pub mod elowen {
         elw::initialize(ctx, &metadata_uri)
     }
 
+    // admin
+    pub fn initialize_admin_config(
+        ctx: Context<InitializeAdminConfig>,
+        roles: AdminRoles,
+    ) -> Result<()> {
+        admin::initialize_config(ctx, roles)
+    }
+
+    pub fn update_admin_roles(ctx: Context<UpdateAdminRoles>, roles: AdminRoles) -> Result<()> {
+        admin::update_roles(ctx, roles)
+    }
+
+    pub fn transfer_admin_ownership(
+        ctx: Context<TransferAdminOwnership>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        admin::transfer_ownership(ctx, new_owner)
+    }
+
+    pub fn accept_admin_ownership(ctx: Context<AcceptAdminOwnership>) -> Result<()> {
+        admin::accept_ownership(ctx)
+    }
+    // admin
+
     // eda
     pub fn withdraw_eda_elw(ctx: Context<WithdrawEdaELW>, amount: u64) -> Result<()> {
         eda::elw::withdraw(ctx, amount)
@@ -55,14 +85,71 @@ pub mod elowen {
     pub fn withdraw_eda_usdc(ctx: Context<WithdrawEdaUSDC>, amount: u64) -> Result<()> {
         eda::usdc::withdraw(ctx, amount)
     }
+
+    pub fn withdraw_eda_token(ctx: Context<WithdrawEdaToken>, amount: u64) -> Result<()> {
+        eda::token::withdraw(ctx, amount)
+    }
     // eda
 
     // team
     pub fn claim_team_elw(ctx: Context<ClaimTeamELW>) -> Result<()> {
         team::claim(ctx)
     }
+
+    pub fn create_team_vesting(
+        ctx: Context<CreateTeamVesting>,
+        total_amount: u64,
+        schedule: VestingSchedule,
+    ) -> Result<()> {
+        team::create_vesting(ctx, total_amount, schedule)
+    }
+
+    pub fn revoke_team_vesting(ctx: Context<RevokeTeamVesting>) -> Result<()> {
+        team::revoke_vesting(ctx)
+    }
+
+    pub fn rotate_team_member(ctx: Context<RotateTeamMember>) -> Result<()> {
+        team::rotate_member(ctx)
+    }
     // team
 
+    // vesting
+    pub fn create_vesting(
+        ctx: Context<CreateVesting>,
+        id: u64,
+        vault: VaultAccount,
+        total_amount: u64,
+        schedule: VestingSchedule,
+        is_revocable: bool,
+        milestones: Vec<GrantMilestone>,
+    ) -> Result<()> {
+        vesting::create(
+            ctx,
+            id,
+            vault,
+            total_amount,
+            schedule,
+            is_revocable,
+            milestones,
+        )
+    }
+
+    pub fn claim_vesting(ctx: Context<ClaimVesting>) -> Result<()> {
+        vesting::claim(ctx)
+    }
+
+    pub fn revoke_vesting(ctx: Context<RevokeVesting>) -> Result<()> {
+        vesting::revoke(ctx)
+    }
+
+    pub fn approve_vesting_milestone(
+        ctx: Context<ApproveVestingMilestone>,
+        index: u8,
+    ) -> Result<()> {
+        vesting::approve_milestone(ctx, index)
+    }
+    // vesting
+
     // platform
     pub fn withdraw_platform_elw(ctx: Context<WithdrawPlatformELW>, amount: u64) -> Result<()> {
         platform::withdraw(ctx, amount)
@@ -74,20 +161,135 @@ pub mod elowen {
     // platform
 
     // presale
+    pub fn initialize_presale_config(
+        ctx: Context<InitializePresaleConfig>,
+        rules: PresaleRules,
+    ) -> Result<()> {
+        presale::initialize_config(ctx, rules)
+    }
+
+    pub fn update_presale_config(
+        ctx: Context<UpdatePresaleConfig>,
+        rules: PresaleRules,
+    ) -> Result<()> {
+        presale::update_config(ctx, rules)
+    }
+
     pub fn buy_presale_elw(
         ctx: Context<BuyPresaleElw>,
         presale_type: PresaleType,
         amount_to_buy: u64,
         currency: Currency,
+        max_payment_amount: u64,
+        allowlist_proof: Option<AllowlistProof>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        presale::buy(
+            ctx,
+            presale_type,
+            amount_to_buy,
+            currency,
+            max_payment_amount,
+            allowlist_proof,
+            referrer,
+        )
+    }
+
+    pub fn buy_presale_elw_with_token(
+        ctx: Context<BuyPresaleElwWithToken>,
+        presale_type: PresaleType,
+        amount_to_buy: u64,
+        max_payment_amount: u64,
+        allowlist_proof: Option<AllowlistProof>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
+        presale::buy_with_token(
+            ctx,
+            presale_type,
+            amount_to_buy,
+            max_payment_amount,
+            allowlist_proof,
+            referrer,
+        )
+    }
+
+    pub fn register_payment_mint(
+        ctx: Context<RegisterPaymentMint>,
+        feed_id: Option<[u8; 32]>,
     ) -> Result<()> {
-        presale::buy(ctx, presale_type, amount_to_buy, currency)
+        presale::register_payment_mint(ctx, feed_id)
+    }
+
+    pub fn update_payment_mint(
+        ctx: Context<UpdatePaymentMint>,
+        feed_id: Option<[u8; 32]>,
+        is_enabled: bool,
+    ) -> Result<()> {
+        presale::update_payment_mint(ctx, feed_id, is_enabled)
     }
 
     pub fn claim_presale_elw(
         ctx: Context<ClaimPresaleElw>,
+        presale_type: PresaleType,
+    ) -> Result<()> {
+        presale::claim(ctx, presale_type)
+    }
+
+    pub fn tokenize_presale_position(
+        ctx: Context<TokenizePresalePosition>,
+        presale_type: PresaleType,
+        amount: u64,
+    ) -> Result<()> {
+        presale::tokenize(ctx, presale_type, amount)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        presale::register_referrer(ctx)
+    }
+
+    pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>) -> Result<()> {
+        presale::claim_referral(ctx)
+    }
+
+    pub fn release_presale_funds(ctx: Context<ReleasePresaleFunds>) -> Result<()> {
+        presale::release(ctx)
+    }
+
+    pub fn release_presale_token_funds(ctx: Context<ReleasePresaleTokenFunds>) -> Result<()> {
+        presale::release_token(ctx)
+    }
+
+    pub fn finalize_presale(ctx: Context<FinalizePresale>) -> Result<()> {
+        presale::finalize(ctx)
+    }
+
+    pub fn refund_presale(ctx: Context<RefundPresale>, _presale_type: PresaleType) -> Result<()> {
+        presale::refund(ctx)
+    }
+
+    pub fn refund_presale_token(
+        ctx: Context<RefundPresaleToken>,
+        _presale_type: PresaleType,
+    ) -> Result<()> {
+        presale::refund_token(ctx)
+    }
+
+    pub fn transfer_presale_position(
+        ctx: Context<TransferPresalePosition>,
+        presale_type: PresaleType,
+    ) -> Result<()> {
+        presale::transfer_position(ctx, presale_type)
+    }
+
+    pub fn migrate_presale_purchase_account(
+        ctx: Context<MigratePurchaseAccount>,
         _presale_type: PresaleType,
     ) -> Result<()> {
-        presale::claim(ctx)
+        presale::migrate(ctx)
+    }
+
+    pub fn migrate_presale_summary_account(ctx: Context<MigrateSummaryAccount>) -> Result<()> {
+        presale::migrate_summary(ctx)
     }
 
     pub fn burn_unsold_presale_elw(ctx: Context<BurnUnsoldElw>) -> Result<()> {
@@ -96,11 +298,57 @@ pub mod elowen {
     // presale
 
     // reward
+    pub fn create_reward_pool(
+        ctx: Context<CreateRewardPool>,
+        id: u8,
+        share_percentage: u16,
+    ) -> Result<()> {
+        reward::create_pool(ctx, id, share_percentage)
+    }
+
+    pub fn update_reward_pool(
+        ctx: Context<UpdateRewardPool>,
+        share_percentage: u16,
+        is_enabled: bool,
+    ) -> Result<()> {
+        reward::update_pool(ctx, share_percentage, is_enabled)
+    }
+
+    pub fn publish_reward_epoch(
+        ctx: Context<PublishRewardEpoch>,
+        epoch: u32,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        leaf_count: u32,
+    ) -> Result<()> {
+        reward::publish_epoch(ctx, epoch, merkle_root, total_amount, leaf_count)
+    }
+
+    pub fn initialize_emission_schedule(
+        ctx: Context<InitializeEmissionSchedule>,
+        schedule: EmissionSchedule,
+    ) -> Result<()> {
+        emission::initialize_schedule(ctx, schedule)
+    }
+
+    pub fn update_emission_schedule(
+        ctx: Context<UpdateEmissionSchedule>,
+        schedule: EmissionSchedule,
+    ) -> Result<()> {
+        emission::update_schedule(ctx, schedule)
+    }
+
+    pub fn get_emission(ctx: Context<GetEmission>, timestamp: i64) -> Result<u64> {
+        emission::get_emission(ctx, timestamp)
+    }
+
     pub fn claim_elw_reward(
         ctx: Context<ClaimReward>,
-        claimable_rewards: Vec<ClaimableReward>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        reward::claim(ctx, claimable_rewards)
+        reward::claim(ctx, index, amount, proof)
     }
     // reward
 
@@ -122,6 +370,31 @@ pub mod elowen {
     }
     // premium
 
+    // withdrawal
+    pub fn set_spending_limit(
+        ctx: Context<SetSpendingLimit>,
+        vault: VaultAccount,
+        mint: Pubkey,
+        limit_amount: u64,
+        period: i64,
+        timelock: i64,
+    ) -> Result<()> {
+        withdrawal::set_limit(ctx, vault, mint, limit_amount, period, timelock)
+    }
+
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, amount: u64) -> Result<()> {
+        withdrawal::queue(ctx, amount)
+    }
+
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        withdrawal::execute(ctx)
+    }
+
+    pub fn cancel_withdrawal(ctx: Context<CancelWithdrawal>) -> Result<()> {
+        withdrawal::cancel(ctx)
+    }
+    // withdrawal
+
     // liquidity
     pub fn initialize_cpmm_liquidity(
         ctx: Context<LiquidityInitialize>,
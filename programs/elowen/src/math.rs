@@ -0,0 +1,168 @@
+// Integer basis point math, intermediates are u128 so 1e18 base unit amounts stay exact
+
+pub const BASIS_POINTS: u64 = 10_000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    Floor,
+    Ceil,
+    // half rounds up
+    Nearest,
+}
+
+// value * numerator / denominator, None for a zero denominator or a result above u64
+pub fn mul_div(value: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let product = value as u128 * numerator as u128;
+    let denominator = denominator as u128;
+    let quotient = product / denominator;
+    let remainder = product % denominator;
+
+    let result = match rounding {
+        Rounding::Floor => quotient,
+        Rounding::Ceil if remainder > 0 => quotient + 1,
+        Rounding::Nearest if remainder * 2 >= denominator => quotient + 1,
+        _ => quotient,
+    };
+
+    u64::try_from(result).ok()
+}
+
+// share of an amount in basis points, saturating above u64
+pub fn apply_bps(amount: u64, bps: u16, rounding: Rounding) -> u64 {
+    mul_div(amount, bps as u64, BASIS_POINTS, rounding).unwrap_or(u64::MAX)
+}
+
+// basis point share and the rest, both always add up to the amount
+pub fn split_bps(amount: u64, bps: u16, rounding: Rounding) -> (u64, u64) {
+    let share = std::cmp::min(apply_bps(amount, bps, rounding), amount);
+    (share, amount - share)
+}
+
+// part of a total in basis points, saturating above u16
+pub fn to_bps(part: u64, total: u64, rounding: Rounding) -> u16 {
+    mul_div(part, BASIS_POINTS, total, rounding)
+        .and_then(|bps| u16::try_from(bps).ok())
+        .unwrap_or(u16::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::SUPPLY;
+
+    const ROUNDINGS: [Rounding; 3] = [Rounding::Floor, Rounding::Ceil, Rounding::Nearest];
+    const EDGE_AMOUNTS: [u64; 4] = [0, 1, u64::MAX, SUPPLY];
+
+    #[test]
+    fn split_bps_adds_up_to_the_amount() {
+        for amount in EDGE_AMOUNTS {
+            for bps in 0..=u16::MAX {
+                for rounding in ROUNDINGS {
+                    let (share, rest) = split_bps(amount, bps, rounding);
+                    assert!(share <= amount);
+                    assert_eq!(share.checked_add(rest), Some(amount));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn split_bps_adds_up_to_random_amounts() {
+        // xorshift64, a fixed seed keeps failures reproducible
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..100_000 {
+            // mix full range and small amounts, rounding differs most on small ones
+            let amount = match next() & 3 {
+                0 => next(),
+                1 => next() >> 32,
+                2 => next() & 0xFFFF,
+                _ => next() % (SUPPLY + 1),
+            };
+            let bps = next() as u16;
+            for rounding in ROUNDINGS {
+                let (share, rest) = split_bps(amount, bps, rounding);
+                assert!(share <= amount);
+                assert_eq!(share.checked_add(rest), Some(amount));
+            }
+        }
+    }
+
+    #[test]
+    fn split_bps_above_the_basis_points_keeps_the_whole_amount() {
+        for amount in EDGE_AMOUNTS {
+            for rounding in ROUNDINGS {
+                assert_eq!(split_bps(amount, u16::MAX, rounding), (amount, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn rounding_modes_stay_within_one_unit() {
+        for amount in [1, 3, 7, 9_999, 10_001, SUPPLY - 1, u64::MAX] {
+            for bps in [1, 3, 3_333, 5_000, 6_667, 9_999, BASIS_POINTS as u16] {
+                let exact = amount as u128 * bps as u128;
+                let floor = apply_bps(amount, bps, Rounding::Floor);
+                let ceil = apply_bps(amount, bps, Rounding::Ceil);
+                let nearest = apply_bps(amount, bps, Rounding::Nearest);
+
+                assert!(floor as u128 * BASIS_POINTS as u128 <= exact);
+                assert!(ceil as u128 * BASIS_POINTS as u128 >= exact);
+                assert!(ceil - floor <= 1);
+                assert_eq!(ceil > floor, floor as u128 * BASIS_POINTS as u128 != exact);
+                assert!(nearest == floor || nearest == ceil);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_rounds_half_up() {
+        assert_eq!(mul_div(1, 1, 2, Rounding::Nearest), Some(1));
+        assert_eq!(mul_div(1, 1, 3, Rounding::Nearest), Some(0));
+        assert_eq!(mul_div(2, 1, 3, Rounding::Nearest), Some(1));
+        assert_eq!(mul_div(5, 1, 2, Rounding::Floor), Some(2));
+        assert_eq!(mul_div(5, 1, 2, Rounding::Ceil), Some(3));
+        assert_eq!(mul_div(5, 1, 2, Rounding::Nearest), Some(3));
+    }
+
+    #[test]
+    fn mul_div_handles_zero_denominator_and_overflow() {
+        for rounding in ROUNDINGS {
+            assert_eq!(mul_div(1, 1, 0, rounding), None);
+            assert_eq!(mul_div(0, 0, 0, rounding), None);
+            assert_eq!(mul_div(u64::MAX, 2, 1, rounding), None);
+            assert_eq!(
+                mul_div(u64::MAX, u64::MAX, u64::MAX, rounding),
+                Some(u64::MAX)
+            );
+            assert_eq!(mul_div(u64::MAX, u64::MAX, 1, rounding), None);
+        }
+        // only the final result has to fit, the u128 intermediate never overflows
+        assert_eq!(mul_div(u64::MAX, 3, 2, Rounding::Floor), None);
+        assert_eq!(mul_div(u64::MAX, 1, 1, Rounding::Ceil), Some(u64::MAX));
+        assert_eq!(apply_bps(u64::MAX, u16::MAX, Rounding::Floor), u64::MAX);
+    }
+
+    #[test]
+    fn to_bps_saturates_on_overflow_and_zero_total() {
+        for rounding in ROUNDINGS {
+            assert_eq!(to_bps(1, 0, rounding), u16::MAX);
+            assert_eq!(to_bps(0, 0, rounding), u16::MAX);
+            assert_eq!(to_bps(u64::MAX, 1, rounding), u16::MAX);
+            assert_eq!(to_bps(0, SUPPLY, rounding), 0);
+            assert_eq!(to_bps(SUPPLY, SUPPLY, rounding), BASIS_POINTS as u16);
+        }
+        assert_eq!(to_bps(1, 3, Rounding::Floor), 3_333);
+        assert_eq!(to_bps(1, 3, Rounding::Ceil), 3_334);
+        assert_eq!(to_bps(2, 3, Rounding::Nearest), 6_667);
+    }
+}
@@ -1,6 +1,11 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::keccak};
 
-use crate::{constants::*, enums::MiningAction, functions::calculate_by_percentage};
+use crate::{
+    constants::*,
+    enums::{CustomError, EmissionCurve, MiningAction, PresaleType, VaultAccount},
+    functions::{get_months_difference, get_months_later, verify_merkle_proof},
+    math::{apply_bps, Rounding},
+};
 
 #[account]
 #[derive(InitSpace)]
@@ -15,6 +20,93 @@ pub struct RewardAccount {
     pub percentage: u16,
 }
 
+// Activity category receiving a share of each epoch emission
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPoolAccount {
+    pub id: u8,
+    pub share_percentage: u16,
+    pub is_enabled: bool,
+    pub published_amount: u64,
+    pub claimed_amount: u64,
+}
+
+// Shares of the enabled reward pools, at most the whole emission
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPoolsAccount {
+    pub total_share_percentage: u16,
+}
+
+// Amount published for an epoch over all pools, at most the month emission
+#[account]
+#[derive(InitSpace)]
+pub struct RewardEpochTotalAccount {
+    pub epoch: u32,
+    pub published_amount: u64,
+}
+
+// Monthly pool distribution published as a merkle root of (index, receiver, amount)
+#[account]
+#[derive(InitSpace)]
+pub struct RewardEpochAccount {
+    pub pool: u8,
+    pub epoch: u32,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub leaf_count: u32,
+    #[max_len(REWARD_EPOCH_BITMAP_LEN)]
+    pub claimed_bitmap: Vec<u8>,
+}
+
+impl RewardEpochAccount {
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed_bitmap[(index / 8) as usize] |= 1 << (index % 8);
+    }
+
+    pub fn verify_claim(
+        &self,
+        index: u32,
+        receiver: &Pubkey,
+        amount: u64,
+        proof: &[[u8; 32]],
+    ) -> bool {
+        let leaf = keccak::hashv(&[
+            &index.to_le_bytes(),
+            receiver.as_ref(),
+            &amount.to_le_bytes(),
+        ])
+        .0;
+        verify_merkle_proof(proof, self.merkle_root, leaf)
+    }
+}
+
+// Pool epochs already claimed by a receiver, whatever leaf the claim came from
+#[account]
+#[derive(InitSpace)]
+pub struct UserRewardAccount {
+    pub receiver: Pubkey,
+    pub pool: u8,
+    pub claimed_epochs: [u8; USER_REWARD_BITMAP_LEN],
+    pub claimed_amount: u64,
+    pub last_claim_time: i64,
+}
+
+impl UserRewardAccount {
+    pub fn is_claimed(&self, epoch: u32) -> bool {
+        self.claimed_epochs[(epoch / 8) as usize] & (1 << (epoch % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, epoch: u32) {
+        self.claimed_epochs[(epoch / 8) as usize] |= 1 << (epoch % 8);
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct PurchaseAccount {
@@ -22,6 +114,115 @@ pub struct PurchaseAccount {
     pub claimed: bool,
     pub unlock_time: i64,
     pub presale_type: u8,
+    pub round_amounts: [u64; MAX_PRESALE_ROUNDS],
+    pub claimed_amount: u64,
+    pub sol_paid: u64,
+    pub usdc_paid: u64,
+}
+
+impl PurchaseAccount {
+    pub fn from_legacy(legacy: LegacyPurchaseAccount) -> Self {
+        Self {
+            amount: legacy.amount,
+            claimed: legacy.claimed,
+            unlock_time: legacy.unlock_time,
+            presale_type: legacy.presale_type,
+            // legacy purchases predate rounds, so they count against no round contribution
+            round_amounts: [0u64; MAX_PRESALE_ROUNDS],
+            claimed_amount: if legacy.claimed { legacy.amount } else { 0 },
+            sol_paid: 0,
+            usdc_paid: 0,
+        }
+    }
+
+    // TGE share at presale end, the rest streams per second after the unlock time
+    pub fn get_vested_amount(&self, rules: &PresaleRules, timestamp: i64) -> u64 {
+        rules
+            .get_vesting_schedule(self.unlock_time)
+            .get_vested_amount(self.amount, timestamp)
+    }
+
+    pub fn get_claimable_amount(&self, rules: &PresaleRules, timestamp: i64) -> u64 {
+        self.get_vested_amount(rules, timestamp)
+            .saturating_sub(self.claimed_amount)
+    }
+}
+
+// Purchase account layout before rounds and vesting, kept for migration
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyPurchaseAccount {
+    pub amount: u64,
+    pub claimed: bool,
+    pub unlock_time: i64,
+    pub presale_type: u8,
+}
+
+// Receipts of a lockup type, redeemed on the same schedule as the positions
+#[account]
+#[derive(InitSpace)]
+pub struct ReceiptSummaryAccount {
+    pub minted_amount: u64,
+    pub burned_amount: u64,
+    pub redeemed_amount: u64,
+}
+
+// Receipts burned by a holder, redeemed at the vested ratio of the lockup type
+#[account]
+#[derive(InitSpace)]
+pub struct ReceiptClaimAccount {
+    pub burned_amount: u64,
+    pub redeemed_amount: u64,
+}
+
+impl ReceiptClaimAccount {
+    // every burned receipt unlocks at the same ratio, wherever it was held before
+    pub fn get_redeemable_amount(&self, schedule: &VestingSchedule, timestamp: i64) -> u64 {
+        schedule
+            .get_vested_amount(self.burned_amount, timestamp)
+            .saturating_sub(self.redeemed_amount)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistAccount {
+    pub purchased_amount: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentMintAccount {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    // stablecoins are pegged 1:1 to USD and have no feed
+    pub feed_id: Option<[u8; 32]>,
+    pub is_enabled: bool,
+    pub raised: u64,
+    pub token_sold: u64,
+    pub refunded: u64,
+    pub sent_to_eda: u64,
+    pub sent_to_liquidity: u64,
+    pub is_funds_released: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PaymentAccount {
+    pub amount: u64,
+    // ELW bought with this mint, removed from the position on refund
+    pub token_bought: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,
+    pub purchase_count: u32,
+    // ELW bought and USDC value paid by referred buyers
+    pub token_volume: u64,
+    pub usdc_volume: u64,
+    pub earned_amount: u64,
+    pub claimed_amount: u64,
 }
 
 #[account]
@@ -38,6 +239,334 @@ pub struct SummaryAccount {
     pub sol_sent_to_liquidity: u64,
     pub usdc_sent_to_liquidity: u64,
     pub is_unsold_tokens_burned: bool,
+    pub round_token_sold: [u64; MAX_PRESALE_ROUNDS],
+    pub sol_refunded: u64,
+    pub usdc_refunded: u64,
+    pub is_funds_released: bool,
+    // reserved from the presale allocation for referrers
+    pub referral_bonus_amount: u64,
+}
+
+impl SummaryAccount {
+    pub fn from_legacy(legacy: LegacySummaryAccount) -> Self {
+        Self {
+            sol_raised: legacy.sol_raised,
+            usdc_raised: legacy.usdc_raised,
+            token_sold: legacy.token_sold,
+            total_amount: legacy.total_amount,
+            sol_sent_to_eda: legacy.sol_sent_to_eda,
+            usdc_sent_to_eda: legacy.usdc_sent_to_eda,
+            token_sold_for_sol: legacy.token_sold_for_sol,
+            token_sold_for_usdc: legacy.token_sold_for_usdc,
+            sol_sent_to_liquidity: legacy.sol_sent_to_liquidity,
+            usdc_sent_to_liquidity: legacy.usdc_sent_to_liquidity,
+            is_unsold_tokens_burned: legacy.is_unsold_tokens_burned,
+            // sales before rounds are not split per round
+            round_token_sold: [0u64; MAX_PRESALE_ROUNDS],
+            sol_refunded: 0,
+            usdc_refunded: 0,
+            // the legacy flow already moved the raised funds out of the escrow
+            is_funds_released: legacy.sol_sent_to_eda > 0
+                || legacy.usdc_sent_to_eda > 0
+                || legacy.sol_sent_to_liquidity > 0
+                || legacy.usdc_sent_to_liquidity > 0,
+            referral_bonus_amount: 0,
+        }
+    }
+
+    // sold tokens are refunded instead of claimed when the soft cap is missed
+    pub fn get_unsold_amount(&self, rules: &PresaleRules) -> u64 {
+        if rules.is_soft_cap_reached(self.token_sold) {
+            self.total_amount - self.token_sold - self.referral_bonus_amount
+        } else {
+            self.total_amount
+        }
+    }
+}
+
+// Summary account layout before rounds, refunds and referrals, kept for migration
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacySummaryAccount {
+    pub sol_raised: u64,
+    pub usdc_raised: u64,
+    pub token_sold: u64,
+    pub total_amount: u64,
+    pub sol_sent_to_eda: u64,
+    pub usdc_sent_to_eda: u64,
+    pub token_sold_for_sol: u64,
+    pub token_sold_for_usdc: u64,
+    pub sol_sent_to_liquidity: u64,
+    pub usdc_sent_to_liquidity: u64,
+    pub is_unsold_tokens_burned: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PresaleRound {
+    pub three_months_lockup_price: u64,
+    pub six_months_lockup_price: u64,
+    pub min_contribution: u64,
+    pub max_contribution: u64,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+impl PresaleRound {
+    pub fn get_remaining_amount(&self, token_sold: u64) -> u64 {
+        self.total_amount.saturating_sub(token_sold)
+    }
+
+    pub fn is_active(&self, timestamp: i64) -> bool {
+        timestamp >= self.start_time && timestamp < self.end_time
+    }
+
+    pub fn get_price_per_token(&self, presale_type: PresaleType) -> u64 {
+        match presale_type {
+            PresaleType::ThreeMonthsLockup => self.three_months_lockup_price,
+            PresaleType::SixMonthsLockup => self.six_months_lockup_price,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct PresaleRules {
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    // minimum sold amount, otherwise buyers are refunded
+    pub soft_cap: u64,
+    // only allowlisted wallets can buy until the public phase, zero root disables it
+    pub allowlist_root: [u8; 32],
+    pub public_start_time: i64,
+    // oracle limits for SOL payments, seconds and basis points of the price
+    pub max_price_age: u64,
+    pub max_price_confidence: u16,
+    // released at presale end, basis points of each purchase
+    pub tge_percentage: u16,
+    // linear release after the unlock time, zero keeps a single cliff
+    pub vesting_duration: i64,
+    // ELW bonus credited to the referrer, basis points of each referred purchase
+    pub referral_bonus_percentage: u16,
+    // Raydium CPMM fee tier of the pool created by the permissionless finalize
+    pub amm_config: Pubkey,
+    // seed, private, public... in chronological order
+    #[max_len(MAX_PRESALE_ROUNDS)]
+    pub rounds: Vec<PresaleRound>,
+}
+
+impl PresaleRules {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.total_amount > 0
+                && self.total_amount <= apply_bps(SUPPLY, PRESALE_PERCENTAGE, Rounding::Floor),
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.start_time < self.end_time,
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.soft_cap <= self.total_amount,
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.allowlist_root == [0u8; 32]
+                || (self.public_start_time >= self.start_time
+                    && self.public_start_time <= self.end_time),
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.max_price_age > 0
+                && self.max_price_confidence > 0
+                && self.max_price_confidence <= 10000,
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.tge_percentage <= 10000 && self.vesting_duration >= 0,
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.referral_bonus_percentage <= 10000,
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            self.amm_config != Pubkey::default(),
+            CustomError::InvalidPresaleConfig
+        );
+        require!(
+            !self.rounds.is_empty() && self.rounds.len() <= MAX_PRESALE_ROUNDS,
+            CustomError::InvalidPresaleConfig
+        );
+
+        let mut previous_end_time = self.start_time;
+        let mut rounds_total_amount: u64 = 0;
+
+        for round in self.rounds.iter() {
+            require!(
+                round.three_months_lockup_price > 0 && round.six_months_lockup_price > 0,
+                CustomError::InvalidPresaleConfig
+            );
+            require!(
+                round.min_contribution <= round.max_contribution,
+                CustomError::InvalidPresaleConfig
+            );
+            // rounds must not overlap and must fit into the presale window
+            require!(
+                round.start_time >= previous_end_time
+                    && round.start_time < round.end_time
+                    && round.end_time <= self.end_time,
+                CustomError::InvalidPresaleConfig
+            );
+            previous_end_time = round.end_time;
+            rounds_total_amount = rounds_total_amount
+                .checked_add(round.total_amount)
+                .ok_or(CustomError::InvalidPresaleConfig)?;
+        }
+
+        require!(
+            rounds_total_amount <= self.total_amount,
+            CustomError::InvalidPresaleConfig
+        );
+
+        Ok(())
+    }
+
+    pub fn get_remaining_amount(&self, token_sold: u64) -> u64 {
+        self.total_amount - token_sold
+    }
+
+    pub fn is_presale_active(&self) -> bool {
+        self.is_presale_started() && !self.is_presale_ended()
+    }
+
+    pub fn is_presale_ended(&self) -> bool {
+        let clock = Clock::get().unwrap();
+        clock.unix_timestamp >= self.end_time
+    }
+
+    pub fn is_presale_started(&self) -> bool {
+        let clock = Clock::get().unwrap();
+        clock.unix_timestamp >= self.start_time
+    }
+
+    pub fn is_soft_cap_reached(&self, token_sold: u64) -> bool {
+        token_sold >= self.soft_cap
+    }
+
+    pub fn is_allowlist_phase(&self) -> bool {
+        let clock = Clock::get().unwrap();
+        self.allowlist_root != [0u8; 32] && clock.unix_timestamp < self.public_start_time
+    }
+
+    // leaf is keccak(receiver, allocation), zero allocation means no extra limit
+    pub fn is_allowlisted(&self, receiver: &Pubkey, allocation: u64, proof: &[[u8; 32]]) -> bool {
+        let leaf = keccak::hashv(&[receiver.as_ref(), &allocation.to_le_bytes()]).0;
+        verify_merkle_proof(proof, self.allowlist_root, leaf)
+    }
+
+    pub fn get_active_round(&self) -> Result<usize> {
+        require!(self.is_presale_started(), CustomError::PresaleIsNotStarted);
+        require!(self.is_presale_active(), CustomError::PresaleIsEnded);
+
+        let current_time = Clock::get()?.unix_timestamp;
+
+        self.rounds
+            .iter()
+            .position(|round| round.is_active(current_time))
+            .ok_or(CustomError::NoActivePresaleRound.into())
+    }
+
+    pub fn conditions<'info>(
+        &self,
+        round_index: usize,
+        amount_to_buy: u64,
+        token_sold: u64,
+        round_token_sold: u64,
+        purchase_amount: u64,
+    ) -> Result<()> {
+        let round = &self.rounds[round_index];
+        let remaining_amount = std::cmp::min(
+            self.get_remaining_amount(token_sold),
+            round.get_remaining_amount(round_token_sold),
+        );
+
+        require!(remaining_amount > 0, CustomError::AllTokensSold);
+
+        require!(
+            amount_to_buy <= remaining_amount,
+            CustomError::ExceedsTheRemainingAmount
+        );
+
+        let min_contribution = if remaining_amount > round.min_contribution {
+            round.min_contribution
+        } else {
+            remaining_amount
+        };
+
+        require!(
+            amount_to_buy >= min_contribution,
+            CustomError::BelowTheMinimumContribution
+        );
+
+        require!(
+            (amount_to_buy + purchase_amount) <= round.max_contribution,
+            CustomError::ExceedsTheMaximumContribution
+        );
+
+        Ok(())
+    }
+
+    // price of the last round for the shorter lockup, used to seed the pools
+    pub fn get_final_price(&self) -> u64 {
+        self.rounds
+            .last()
+            .map(|round| round.three_months_lockup_price)
+            .unwrap_or_default()
+    }
+
+    pub fn get_unlock_time(&self, presale_type: PresaleType) -> i64 {
+        match presale_type {
+            PresaleType::ThreeMonthsLockup => get_months_later(self.end_time, 3),
+            PresaleType::SixMonthsLockup => get_months_later(self.end_time, 6),
+        }
+    }
+
+    pub fn get_vesting_schedule(&self, unlock_time: i64) -> VestingSchedule {
+        VestingSchedule {
+            cliff_time: self.end_time,
+            start_time: unlock_time,
+            end_time: unlock_time + self.vesting_duration,
+            tge_percentage: self.tge_percentage,
+        }
+    }
+
+    pub fn get_vesting_end_time(&self, presale_type: PresaleType) -> i64 {
+        self.get_unlock_time(presale_type) + self.vesting_duration
+    }
+
+    pub fn calculate_payment_amount(
+        &self,
+        amount_to_buy: u64,
+        presale_type: PresaleType,
+        round_index: usize,
+    ) -> u64 {
+        let price_per_token = self.rounds[round_index].get_price_per_token(presale_type);
+        (amount_to_buy as u128)
+            .checked_mul(price_per_token as u128)
+            .unwrap()
+            .checked_div(10u128.pow(9 - 6))
+            .unwrap()
+            .checked_div(10u128.pow(6))
+            .unwrap() as u64
+    }
+}
+
+// Presale schedule and prices, editable by the multisig until the sale opens
+#[account]
+#[derive(InitSpace)]
+pub struct PresaleConfigAccount {
+    pub rules: PresaleRules,
+    pub is_frozen: bool,
 }
 
 #[account]
@@ -47,6 +576,34 @@ pub struct MemberClaimAccount {
     pub last_period: i64,
 }
 
+// Team member vesting, created and revoked by the multisig
+#[account]
+#[derive(InitSpace)]
+pub struct TeamVestingAccount {
+    pub member: Pubkey,
+    pub total_amount: u64,
+    pub schedule: VestingSchedule,
+    pub is_revoked: bool,
+}
+
+impl TeamVestingAccount {
+    pub fn get_vested_amount(&self, timestamp: i64) -> u64 {
+        // total is cut down to the vested amount on revoke
+        if self.is_revoked {
+            return self.total_amount;
+        }
+
+        self.schedule
+            .get_vested_amount(self.total_amount, timestamp)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TeamStateAccount {
+    pub allocated_amount: u64,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct LpStateAccount {
@@ -139,8 +696,16 @@ impl MiningStateAccount {
 
     fn get_yearly_reward(&self, reward_amount: u64) -> u64 {
         std::cmp::min(
-            calculate_by_percentage(reward_amount, MINING_YEARLY_ELW_REWARD_PERCENTAGE),
-            calculate_by_percentage(self.elw_amount, MINING_YEARLY_ELW_REWARD_MAX_PERCENTAGE),
+            apply_bps(
+                reward_amount,
+                MINING_YEARLY_ELW_REWARD_PERCENTAGE,
+                Rounding::Floor,
+            ),
+            apply_bps(
+                self.elw_amount,
+                MINING_YEARLY_ELW_REWARD_MAX_PERCENTAGE,
+                Rounding::Floor,
+            ),
         )
     }
 
@@ -193,3 +758,306 @@ impl MinerStateAccount {
         self.quote_amount = self.quote_amount.saturating_sub(quote_amount);
     }
 }
+
+// Shared vesting math, the TGE share unlocks at the cliff and the rest
+// streams per second from start to end, catching up at the cliff
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct VestingSchedule {
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub tge_percentage: u16,
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.start_time <= self.end_time
+                && self.cliff_time <= self.end_time
+                && self.tge_percentage <= 10000,
+            CustomError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+
+    pub fn get_vested_amount(&self, total_amount: u64, timestamp: i64) -> u64 {
+        if timestamp < self.cliff_time {
+            return 0;
+        }
+
+        if timestamp >= self.end_time {
+            return total_amount;
+        }
+
+        let tge_amount = apply_bps(total_amount, self.tge_percentage, Rounding::Floor);
+
+        if timestamp <= self.start_time {
+            return tge_amount;
+        }
+
+        let elapsed = (timestamp - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        let streamed_amount = (total_amount - tge_amount) as u128 * elapsed / duration;
+
+        tge_amount + streamed_amount as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct GrantMilestone {
+    pub amount: u64,
+    // hash of the off-chain milestone description
+    pub description_hash: [u8; 32],
+    pub is_approved: bool,
+}
+
+// Vesting funded from a spending limited vault, tokens are held by the vesting PDA.
+// Grants add milestones, the vested tokens unlock only as far as the approved ones
+#[account]
+#[derive(InitSpace)]
+pub struct VestingAccount {
+    pub id: u64,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    // vault that funded the vesting, its spending limit is credited on revoke
+    pub vault: VaultAccount,
+    // token account that funded the vesting, receives unvested tokens on revoke
+    pub funder: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub schedule: VestingSchedule,
+    // empty for plain vestings
+    #[max_len(MAX_GRANT_MILESTONES)]
+    pub milestones: Vec<GrantMilestone>,
+    pub is_revocable: bool,
+    pub is_revoked: bool,
+}
+
+impl VestingAccount {
+    pub fn get_approved_amount(&self) -> u64 {
+        self.milestones
+            .iter()
+            .filter(|milestone| milestone.is_approved)
+            .map(|milestone| milestone.amount)
+            .sum()
+    }
+
+    pub fn get_vested_amount(&self, timestamp: i64) -> u64 {
+        // total is cut down to the vested amount on revoke
+        if self.is_revoked {
+            return self.total_amount;
+        }
+
+        let vested_amount = self
+            .schedule
+            .get_vested_amount(self.total_amount, timestamp);
+
+        if self.milestones.is_empty() {
+            return vested_amount;
+        }
+
+        std::cmp::min(vested_amount, self.get_approved_amount())
+    }
+
+    pub fn get_claimable_amount(&self, timestamp: i64) -> u64 {
+        self.get_vested_amount(timestamp)
+            .saturating_sub(self.claimed_amount)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct SpendRecord {
+    pub timestamp: i64,
+    pub amount: u64,
+}
+
+// Loosened limit, applied once the timelock of the current limit elapsed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct PendingSpendingLimit {
+    pub limit_amount: u64,
+    pub period: i64,
+    pub timelock: i64,
+    pub effective_after: i64,
+}
+
+// Withdrawal limit of a vault for one mint over a rolling period, WSOL mint stands for SOL
+#[account]
+#[derive(InitSpace)]
+pub struct SpendingLimitAccount {
+    pub vault: VaultAccount,
+    pub mint: Pubkey,
+    pub limit_amount: u64,
+    pub period: i64,
+    pub timelock: i64,
+    pub request_count: u64,
+    pub pending_limit: Option<PendingSpendingLimit>,
+    // withdrawals of the last period, oldest first
+    #[max_len(MAX_SPENDING_RECORDS)]
+    pub spends: Vec<SpendRecord>,
+}
+
+impl SpendingLimitAccount {
+    pub fn apply_pending_limit(&mut self, timestamp: i64) {
+        if let Some(pending_limit) = self.pending_limit {
+            if timestamp >= pending_limit.effective_after {
+                self.limit_amount = pending_limit.limit_amount;
+                self.period = pending_limit.period;
+                self.timelock = pending_limit.timelock;
+                self.pending_limit = None;
+            }
+        }
+    }
+
+    pub fn get_spent_amount(&self, timestamp: i64) -> u64 {
+        self.spends
+            .iter()
+            .filter(|spend| spend.timestamp > timestamp - self.period)
+            .map(|spend| spend.amount)
+            .sum()
+    }
+
+    pub fn spend(&mut self, amount: u64, timestamp: i64) -> Result<()> {
+        self.apply_pending_limit(timestamp);
+
+        // any window of one period stays within the limit
+        let period_start = timestamp - self.period;
+        self.spends.retain(|spend| spend.timestamp > period_start);
+
+        require!(
+            self.spends.len() < MAX_SPENDING_RECORDS
+                && self.get_spent_amount(timestamp).saturating_add(amount) <= self.limit_amount,
+            CustomError::ExceedsTheSpendingLimit
+        );
+
+        self.spends.push(SpendRecord { timestamp, amount });
+
+        Ok(())
+    }
+
+    // funds returned to the vault free the limit again, newest spends first
+    pub fn restore(&mut self, amount: u64, timestamp: i64) {
+        let period_start = timestamp - self.period;
+        self.spends.retain(|spend| spend.timestamp > period_start);
+
+        let mut remaining_amount = amount;
+        for spend in self.spends.iter_mut().rev() {
+            let restored_amount = std::cmp::min(spend.amount, remaining_amount);
+            spend.amount -= restored_amount;
+            remaining_amount -= restored_amount;
+        }
+
+        self.spends.retain(|spend| spend.amount > 0);
+    }
+}
+
+// Withdrawal above the spending limit, executable once the timelock elapsed
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalRequestAccount {
+    pub id: u64,
+    pub spending_limit: Pubkey,
+    pub receiver: Pubkey,
+    pub amount: u64,
+    pub execute_after: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AdminRoles {
+    // platform wallet co-signing user actions, holds no funds
+    pub operator: Pubkey,
+    // withdraws from the EDA, treasury and platform vaults
+    pub treasurer: Pubkey,
+    // co-signs the reward claims
+    pub reward_distributor: Pubkey,
+    // manages the protocol owned liquidity
+    pub liquidity_manager: Pubkey,
+}
+
+// Role keys checked by the instructions, the owner manages the roles
+#[account]
+#[derive(InitSpace)]
+pub struct AdminConfigAccount {
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
+    pub roles: AdminRoles,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EmissionStep {
+    // months after the presale end
+    pub from_month: u32,
+    pub amount: u64,
+}
+
+// Monthly reward emission counted from the presale end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct EmissionSchedule {
+    pub curve: EmissionCurve,
+    // emission of the first month, halving and linear decay
+    pub base_amount: u64,
+    // months between two halvings
+    pub halving_months: u32,
+    // monthly decrease and floor of the linear decay
+    pub decay_amount: u64,
+    pub min_amount: u64,
+    // emission from each month on, ordered by month
+    #[max_len(MAX_EMISSION_STEPS)]
+    pub steps: Vec<EmissionStep>,
+}
+
+impl EmissionSchedule {
+    pub fn validate(&self) -> Result<()> {
+        match self.curve {
+            EmissionCurve::Halving => require!(
+                self.base_amount > 0 && self.halving_months > 0,
+                CustomError::InvalidEmissionSchedule
+            ),
+            EmissionCurve::LinearDecay => require!(
+                self.base_amount > 0 && self.min_amount <= self.base_amount,
+                CustomError::InvalidEmissionSchedule
+            ),
+            EmissionCurve::Steps => require!(
+                !self.steps.is_empty()
+                    && self.steps.len() <= MAX_EMISSION_STEPS
+                    && self
+                        .steps
+                        .windows(2)
+                        .all(|steps| steps[0].from_month < steps[1].from_month),
+                CustomError::InvalidEmissionSchedule
+            ),
+        }
+        Ok(())
+    }
+
+    pub fn get_emission(&self, start_time: i64, timestamp: i64) -> u64 {
+        if timestamp < start_time {
+            return 0;
+        }
+
+        let months = get_months_difference(start_time, timestamp) as u32;
+
+        match self.curve {
+            EmissionCurve::Halving => self
+                .base_amount
+                .checked_shr(months / self.halving_months)
+                .unwrap_or(0),
+            EmissionCurve::LinearDecay => std::cmp::max(
+                self.base_amount
+                    .saturating_sub(self.decay_amount.saturating_mul(months as u64)),
+                self.min_amount,
+            ),
+            EmissionCurve::Steps => self
+                .steps
+                .iter()
+                .rev()
+                .find(|step| step.from_month <= months)
+                .map_or(0, |step| step.amount),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct EmissionScheduleAccount {
+    pub schedule: EmissionSchedule,
+}
@@ -0,0 +1,103 @@
+import { expect } from 'chai'
+import * as anchor from '@coral-xyz/anchor'
+import { clientWallet, getTestAdminRoles } from '../common'
+import { createFundedWallet } from '../wallet'
+import { executeMultisigTransaction } from '../multisig'
+import {
+    ErrorCode,
+    createAcceptAdminOwnershipTransaction,
+    createTransferAdminOwnershipTransaction,
+    createUpdateAdminRolesTransaction,
+    getAdminConfigAccountData,
+    getMultisigVaultPda,
+    signAndSendTransaction
+} from '../../app'
+
+describe('Admin Ownership', () => {
+    let newOwner: anchor.Wallet
+
+    before(async () => {
+        newOwner = await createFundedWallet()
+    })
+
+    it('Transfer the ownership without the owner', async () => {
+        try {
+            const transaction = await createTransferAdminOwnershipTransaction(
+                clientWallet.publicKey,
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the owner transfers the ownership')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+    })
+
+    it('Transfer the ownership', async () => {
+        const txSig = await executeMultisigTransaction(
+            'test admin ownership transfer',
+            await createTransferAdminOwnershipTransaction(newOwner.publicKey)
+        )
+        console.log('Your transaction signature', txSig)
+
+        // the owner stays in charge until the new one accepts
+        const adminConfig = await getAdminConfigAccountData()
+        expect(adminConfig?.owner.toBase58()).to.be.equal(getMultisigVaultPda().toBase58())
+        expect(adminConfig?.pendingOwner?.toBase58()).to.be.equal(newOwner.publicKey.toBase58())
+    })
+
+    it('Accept the ownership with another wallet', async () => {
+        try {
+            const transaction = await createAcceptAdminOwnershipTransaction(clientWallet.publicKey)
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the pending owner accepts')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+    })
+
+    it('Accept the ownership', async () => {
+        const txSig = await signAndSendTransaction(
+            await createAcceptAdminOwnershipTransaction(newOwner.publicKey),
+            [],
+            newOwner
+        )
+        console.log('Your transaction signature', txSig)
+
+        const adminConfig = await getAdminConfigAccountData()
+        expect(adminConfig?.owner.toBase58()).to.be.equal(newOwner.publicKey.toBase58())
+        expect(adminConfig?.pendingOwner).to.be.equal(null)
+    })
+
+    it('Update the roles with the previous owner', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test admin roles previous owner',
+                await createUpdateAdminRolesTransaction(getTestAdminRoles())
+            )
+            expect.fail('The previous owner lost its rights')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+    })
+
+    it('Transfer the ownership back to the multisig', async () => {
+        await signAndSendTransaction(
+            await createTransferAdminOwnershipTransaction(
+                getMultisigVaultPda(),
+                newOwner.publicKey
+            ),
+            [],
+            newOwner
+        )
+        const txSig = await executeMultisigTransaction(
+            'test admin ownership accept',
+            await createAcceptAdminOwnershipTransaction(getMultisigVaultPda())
+        )
+        console.log('Your transaction signature', txSig)
+
+        const adminConfig = await getAdminConfigAccountData()
+        expect(adminConfig?.owner.toBase58()).to.be.equal(getMultisigVaultPda().toBase58())
+        expect(adminConfig?.pendingOwner).to.be.equal(null)
+    })
+})
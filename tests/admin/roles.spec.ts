@@ -0,0 +1,58 @@
+import { expect } from 'chai'
+import { clientWallet, getTestAdminRoles } from '../common'
+import { executeMultisigTransaction } from '../multisig'
+import {
+    ErrorCode,
+    createUpdateAdminRolesTransaction,
+    getAdminConfigAccountData,
+    signAndSendTransaction
+} from '../../app'
+
+describe('Admin Roles', () => {
+    after(async () => {
+        // the other specs expect the test roles
+        await executeMultisigTransaction(
+            'test admin roles restore',
+            await createUpdateAdminRolesTransaction(getTestAdminRoles())
+        )
+    })
+
+    it('Update the roles without the owner', async () => {
+        try {
+            const transaction = await createUpdateAdminRolesTransaction(
+                { ...getTestAdminRoles(), treasurer: clientWallet.publicKey },
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the owner updates the roles')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+
+        const adminConfig = await getAdminConfigAccountData()
+        expect(adminConfig?.roles.treasurer.toBase58()).to.be.equal(
+            getTestAdminRoles().treasurer.toString()
+        )
+    })
+
+    it('Update the roles', async () => {
+        const roles = { ...getTestAdminRoles(), operator: clientWallet.publicKey }
+        const txSig = await executeMultisigTransaction(
+            'test admin roles update',
+            await createUpdateAdminRolesTransaction(roles)
+        )
+        console.log('Your transaction signature', txSig)
+
+        const adminConfig = await getAdminConfigAccountData()
+        expect(adminConfig?.roles.operator.toBase58()).to.be.equal(
+            clientWallet.publicKey.toBase58()
+        )
+        expect(adminConfig?.roles.treasurer.toBase58()).to.be.equal(roles.treasurer.toString())
+        expect(adminConfig?.roles.rewardDistributor.toBase58()).to.be.equal(
+            roles.rewardDistributor.toString()
+        )
+        expect(adminConfig?.roles.liquidityManager.toBase58()).to.be.equal(
+            roles.liquidityManager.toString()
+        )
+    })
+})
@@ -3,6 +3,8 @@ import 'dotenv/config'
 import { Keypair } from '@solana/web3.js'
 import * as anchor from '@coral-xyz/anchor'
 import { initializeProgram } from '../app/program'
+import { getMultisigVaultPda } from '../app/utils'
+import { AdminRoles } from '../app/types'
 import { Connection, Cluster } from '@solana/web3.js'
 import { bs58 } from '@coral-xyz/anchor/dist/cjs/utils/bytes'
 
@@ -23,6 +25,17 @@ export const clientWallet2 = new anchor.Wallet(Keypair.fromSecretKey(clientSecre
 const emptyWalletSecretKey = Buffer.from(bs58.decode(process.env.EMPTY_WALLET as string))
 export const emptyWallet = new anchor.Wallet(Keypair.fromSecretKey(emptyWalletSecretKey))
 
+// the specs sign the treasurer actions with the multisig vault, the rest with the platform wallet
+export const getTestAdminRoles = (): AdminRoles => {
+    const platformWallet = anchor.Wallet.local().publicKey
+    return {
+        operator: platformWallet,
+        treasurer: getMultisigVaultPda(),
+        rewardDistributor: platformWallet,
+        liquidityManager: platformWallet
+    }
+}
+
 export const addArg = (key: string, value: any) => {
     if (!fs.existsSync('./args.json')) {
         fs.writeFileSync('./args.json', '{}')
@@ -1,39 +1,85 @@
 import { expect } from 'chai'
-import { clientWallet, clientWallet2 } from '../common'
-import ElowenProgram, {
+import * as anchor from '@coral-xyz/anchor'
+import { clientWallet } from '../common'
+import { createFundedWallet } from '../wallet'
+import { executeMultisigTransaction } from '../multisig'
+import { getAssociatedTokenAddressSync } from '@solana/spl-token'
+import {
     Currency,
     ErrorCode,
-    createProposalApproveTransaction,
-    createProposalCreateTransaction,
+    VaultAccount,
+    WSOL_MINT,
+    createCancelWithdrawalTransaction,
+    createExecuteWithdrawalTransaction,
+    createQueueWithdrawalTransaction,
+    createSetSpendingLimitTransaction,
     createWithdrawEdaFundTransactionByCurrency,
-    signAndSendTransaction
+    getElwMint,
+    getQuoteMint,
+    getSpendingLimitAccountData,
+    getTokenAccountInfo,
+    getWithdrawalRequestAccountData
 } from '../../app'
 
+const elwLimit = 10
+const period = 86400
+const timelock = 20
+
 describe('EDA', () => {
+    before(async () => {
+        // multisig proposals are indexed, so they go one by one
+        await executeMultisigTransaction(
+            'test eda elw spending limit',
+            await createSetSpendingLimitTransaction(
+                VaultAccount.Eda,
+                await getElwMint(),
+                elwLimit,
+                period,
+                timelock
+            )
+        )
+        await executeMultisigTransaction(
+            'test eda usdc spending limit',
+            await createSetSpendingLimitTransaction(
+                VaultAccount.Eda,
+                getQuoteMint(Currency.USDC),
+                10,
+                period,
+                timelock,
+                6
+            )
+        )
+        await executeMultisigTransaction(
+            'test eda sol spending limit',
+            await createSetSpendingLimitTransaction(
+                VaultAccount.Eda,
+                WSOL_MINT,
+                0.1,
+                period,
+                timelock
+            )
+        )
+
+        const spendingLimit = await getSpendingLimitAccountData(
+            VaultAccount.Eda,
+            await getElwMint()
+        )
+        expect(spendingLimit?.limitAmount).to.be.equal(elwLimit)
+        expect(spendingLimit?.period).to.be.equal(period)
+        expect(spendingLimit?.timelock).to.be.equal(timelock)
+    })
+
     it('Withdraw ELW', async () => {
         let result: any
         try {
-            const { transaction, transactionIndex } = await createProposalCreateTransaction(
+            const txSig = await executeMultisigTransaction(
                 'test eda elw withdraw',
-                ElowenProgram.wallet.publicKey,
                 await createWithdrawEdaFundTransactionByCurrency(
                     clientWallet.publicKey, // just receiver
                     1,
                     Currency.ELW
                 )
             )
-            await signAndSendTransaction(transaction)
-            const transaction1 = await createProposalApproveTransaction(
-                clientWallet.publicKey,
-                transactionIndex
-            )
-            const transaction2 = await createProposalApproveTransaction(
-                clientWallet2.publicKey,
-                transactionIndex,
-                true // execute transaction
-            )
-            await signAndSendTransaction(transaction1, [], clientWallet)
-            const txSig = await signAndSendTransaction(transaction2, [], clientWallet2)
             console.log('Your transaction signature', txSig)
             result = true
         } catch (error: any) {
@@ -45,27 +91,14 @@ describe('EDA', () => {
     it('Withdraw USDC', async () => {
         let result: any
         try {
-            const { transaction, transactionIndex } = await createProposalCreateTransaction(
+            const txSig = await executeMultisigTransaction(
                 'test eda usdc withdraw',
-                ElowenProgram.wallet.publicKey,
                 await createWithdrawEdaFundTransactionByCurrency(
                     clientWallet.publicKey, // just receiver
                     1,
                     Currency.USDC
                 )
             )
-            await signAndSendTransaction(transaction)
-            const transaction1 = await createProposalApproveTransaction(
-                clientWallet.publicKey,
-                transactionIndex
-            )
-            const transaction2 = await createProposalApproveTransaction(
-                clientWallet2.publicKey,
-                transactionIndex,
-                true // execute transaction
-            )
-            await signAndSendTransaction(transaction1, [], clientWallet)
-            const txSig = await signAndSendTransaction(transaction2, [], clientWallet2)
             console.log('Your transaction signature', txSig)
             result = true
         } catch (error: any) {
@@ -76,27 +109,14 @@ describe('EDA', () => {
     it('Withdraw SOL', async () => {
         let result: any
         try {
-            const { transaction, transactionIndex } = await createProposalCreateTransaction(
+            const txSig = await executeMultisigTransaction(
                 'test eda sol withdraw',
-                ElowenProgram.wallet.publicKey,
                 await createWithdrawEdaFundTransactionByCurrency(
                     clientWallet.publicKey, // just receiver
                     0.01,
                     Currency.SOL
                 )
             )
-            await signAndSendTransaction(transaction)
-            const transaction1 = await createProposalApproveTransaction(
-                clientWallet.publicKey,
-                transactionIndex
-            )
-            const transaction2 = await createProposalApproveTransaction(
-                clientWallet2.publicKey,
-                transactionIndex,
-                true // execute transaction
-            )
-            await signAndSendTransaction(transaction1, [], clientWallet)
-            const txSig = await signAndSendTransaction(transaction2, [], clientWallet2)
             console.log('Your transaction signature', txSig)
             result = true
         } catch (error: any) {
@@ -104,4 +124,133 @@ describe('EDA', () => {
         }
         expect(result).to.be.equal(true)
     })
+    it('Withdraw ELW above the spending limit', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test eda elw withdraw above limit',
+                await createWithdrawEdaFundTransactionByCurrency(
+                    clientWallet.publicKey, // just receiver
+                    elwLimit + 1,
+                    Currency.ELW
+                )
+            )
+            expect.fail('Withdrawals above the limit have to be queued')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.ExceedsTheSpendingLimit)
+        }
+    })
+    it('Queue an ELW withdrawal within the spending limit', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test eda elw queue within limit',
+                await createQueueWithdrawalTransaction(
+                    VaultAccount.Eda,
+                    await getElwMint(),
+                    clientWallet.publicKey,
+                    0.5
+                )
+            )
+            expect.fail('Withdrawals within the limit are not queued')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidWithdrawalAmount)
+        }
+    })
+})
+
+describe('EDA Queued Withdrawal', () => {
+    const amount = elwLimit * 2
+    let receiver: anchor.Wallet
+
+    const queueWithdrawal = async (memo: string) => {
+        const elwMint = await getElwMint()
+        const { requestCount } = (await getSpendingLimitAccountData(VaultAccount.Eda, elwMint))!
+        await executeMultisigTransaction(
+            memo,
+            await createQueueWithdrawalTransaction(
+                VaultAccount.Eda,
+                elwMint,
+                receiver.publicKey,
+                amount
+            )
+        )
+        return requestCount
+    }
+
+    const getElwBalance = async () => {
+        const result = await getTokenAccountInfo(
+            getAssociatedTokenAddressSync(await getElwMint(), receiver.publicKey)
+        )
+        return result?.parsed.info.tokenAmount.uiAmount ?? 0
+    }
+
+    before(async () => {
+        receiver = await createFundedWallet()
+    })
+
+    it('Queue, then execute an ELW withdrawal after the timelock', async () => {
+        const elwMint = await getElwMint()
+        const queuedAt = Math.floor(Date.now() / 1000)
+        const id = await queueWithdrawal('test eda elw queue')
+
+        const request = await getWithdrawalRequestAccountData(VaultAccount.Eda, elwMint, id)
+        expect(request?.id).to.be.equal(id)
+        expect(request?.amount).to.be.equal(amount)
+        expect(request?.receiver.toBase58()).to.be.equal(receiver.publicKey.toBase58())
+        expect(request?.executeAfter).to.be.greaterThanOrEqual(queuedAt + timelock - 5)
+        const spendingLimit = await getSpendingLimitAccountData(VaultAccount.Eda, elwMint)
+        expect(spendingLimit?.requestCount).to.be.equal(id + 1)
+
+        try {
+            await executeMultisigTransaction(
+                'test eda elw execute early',
+                await createExecuteWithdrawalTransaction(VaultAccount.Eda, elwMint, id)
+            )
+            expect.fail('A queued withdrawal waits for the timelock')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.TimelockNotElapsed)
+        }
+
+        const waitMs = ((request?.executeAfter ?? 0) + 2) * 1000 - Date.now()
+        if (waitMs > 0) {
+            await new Promise((resolve) => setTimeout(resolve, waitMs))
+        }
+
+        const balanceBefore = await getElwBalance()
+        const txSig = await executeMultisigTransaction(
+            'test eda elw execute',
+            await createExecuteWithdrawalTransaction(VaultAccount.Eda, elwMint, id)
+        )
+        console.log('Your transaction signature', txSig)
+
+        // the request is paid once and closed
+        expect((await getElwBalance()) - balanceBefore).to.be.equal(amount)
+        expect(await getWithdrawalRequestAccountData(VaultAccount.Eda, elwMint, id)).to.be.equal(
+            null
+        )
+    })
+
+    it('Queue, then cancel an ELW withdrawal', async () => {
+        const elwMint = await getElwMint()
+        const id = await queueWithdrawal('test eda elw queue to cancel')
+        const request = await getWithdrawalRequestAccountData(VaultAccount.Eda, elwMint, id)
+        expect(request?.amount).to.be.equal(amount)
+
+        const txSig = await executeMultisigTransaction(
+            'test eda elw cancel',
+            await createCancelWithdrawalTransaction(VaultAccount.Eda, elwMint, id)
+        )
+        console.log('Your transaction signature', txSig)
+
+        // nothing was paid and the request can not be executed anymore
+        expect(await getWithdrawalRequestAccountData(VaultAccount.Eda, elwMint, id)).to.be.equal(
+            null
+        )
+        try {
+            await createExecuteWithdrawalTransaction(VaultAccount.Eda, elwMint, id)
+            expect.fail('A canceled withdrawal is closed')
+        } catch (error: any) {
+            expect(error.message).to.include('Account does not exist')
+        }
+        expect(await getElwBalance()).to.be.equal(amount)
+    })
 })
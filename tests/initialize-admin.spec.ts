@@ -0,0 +1,44 @@
+import { expect } from 'chai'
+import { executeMultisigTransaction } from './multisig'
+import { clientWallet, getTestAdminRoles } from './common'
+import {
+    createInitializeAdminConfigTransaction,
+    getAdminConfigAccountData,
+    getMultisigVaultPda,
+    signAndSendTransaction,
+    ErrorCode
+} from '../app'
+
+// runs before the other specs, every gated instruction reads the admin config
+describe('Initialize admin config', () => {
+    it('Try with wrong signer', async () => {
+        try {
+            const transaction = await createInitializeAdminConfigTransaction(
+                getTestAdminRoles(),
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the multisig initializes the admin config')
+        } catch (error: any) {
+            expect(error.message).to.be.oneOf([ErrorCode.Unauthorized, ErrorCode.PdaAlreadyInUse])
+        }
+    })
+
+    it('Try with correct signer', async () => {
+        let result: any
+        try {
+            const txSig = await executeMultisigTransaction(
+                'test admin config initialize',
+                await createInitializeAdminConfigTransaction(getTestAdminRoles())
+            )
+            console.log('Your transaction signature', txSig)
+            result = true
+        } catch (error: any) {
+            result = error.message === ErrorCode.PdaAlreadyInUse
+        }
+        expect(result).to.be.equal(true)
+
+        const adminConfig = await getAdminConfigAccountData()
+        expect(adminConfig?.owner.toBase58()).to.be.equal(getMultisigVaultPda().toBase58())
+    })
+})
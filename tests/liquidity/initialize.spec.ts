@@ -22,6 +22,7 @@ const totalUsdcValue = totalSolUsdc + usdcSupply
 const elwForSolPool = initialElwAmount * (totalSolUsdc / totalUsdcValue)
 const elwForUsdcPool = initialElwAmount - elwForSolPool
 
+// manual pools are only for a failed presale or a currency the presale did not raise
 describe('Liquidity Initialize', () => {
     it('Initialize with USDC', async () => {
         let result: any
@@ -36,7 +37,11 @@ describe('Liquidity Initialize', () => {
             console.log('Your transaction signature', txSig)
             result = true
         } catch (error: any) {
-            result = error.message === ErrorCode.PdaAlreadyInUse
+            result =
+                error.message === ErrorCode.PdaAlreadyInUse ||
+                error.message === ErrorCode.PresaleIsNotEnded ||
+                error.message === ErrorCode.PoolAlreadyCreated ||
+                error.message === ErrorCode.LiquidityOwnedByPresale
         }
         expect(result).to.be.equal(true)
     })
@@ -53,7 +58,11 @@ describe('Liquidity Initialize', () => {
             console.log('Your transaction signature', txSig)
             result = true
         } catch (error: any) {
-            result = error.message === ErrorCode.PdaAlreadyInUse
+            result =
+                error.message === ErrorCode.PdaAlreadyInUse ||
+                error.message === ErrorCode.PresaleIsNotEnded ||
+                error.message === ErrorCode.PoolAlreadyCreated ||
+                error.message === ErrorCode.LiquidityOwnedByPresale
         }
         expect(result).to.be.equal(true)
     })
@@ -0,0 +1,31 @@
+import { clientWallet, clientWallet2 } from './common'
+import { Transaction, VersionedTransaction } from '@solana/web3.js'
+import ElowenProgram, {
+    createProposalApproveTransaction,
+    createProposalCreateTransaction,
+    signAndSendTransaction
+} from '../app'
+
+// proposes, approves and executes a transaction signed by the multisig vault
+export const executeMultisigTransaction = async (
+    memo: string,
+    _transaction: Transaction | VersionedTransaction
+) => {
+    const { transaction, transactionIndex } = await createProposalCreateTransaction(
+        memo,
+        ElowenProgram.wallet.publicKey,
+        _transaction
+    )
+    await signAndSendTransaction(transaction)
+    const transaction1 = await createProposalApproveTransaction(
+        clientWallet.publicKey,
+        transactionIndex
+    )
+    const transaction2 = await createProposalApproveTransaction(
+        clientWallet2.publicKey,
+        transactionIndex,
+        true // execute transaction
+    )
+    await signAndSendTransaction(transaction1, [], clientWallet)
+    return await signAndSendTransaction(transaction2, [], clientWallet2)
+}
@@ -0,0 +1,86 @@
+import { expect } from 'chai'
+import { clientWallet, getArg } from '../common'
+import {
+    AllowlistProof,
+    Currency,
+    ErrorCode,
+    PresaleType,
+    createBuyPresaleElwTransaction,
+    getPresaleAllowlistAccountData,
+    getPresaleStatus,
+    signAndSendTransaction
+} from '../../app'
+
+const maxUsdcPayment = 10_000
+
+const buy = async (amountToBuy: number, allowlistProof: AllowlistProof | null) => {
+    const transaction = await createBuyPresaleElwTransaction(
+        clientWallet.publicKey,
+        amountToBuy,
+        Currency.USDC,
+        PresaleType.ThreeMonthsLockup,
+        maxUsdcPayment,
+        allowlistProof
+    )
+    return await signAndSendTransaction(transaction, [], clientWallet)
+}
+
+describe('Presale Allowlist', () => {
+    // proof of the client wallet in the tree of the configured root
+    const allowlistProof: AllowlistProof | null = getArg('allowlistProof')
+    const amountToBuy = allowlistProof?.allocation || 1_000_000
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+
+    before(async function () {
+        status = await getPresaleStatus()
+        // only the allowlist phase checks the proofs
+        if (!status?.isStarted || !status.isAllowlistPhase) {
+            this.skip()
+        }
+    })
+
+    it('Buy without a proof', async () => {
+        try {
+            await buy(amountToBuy, null)
+            expect.fail('Only allowlisted wallets buy before the public phase')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.NotInAllowlist)
+        }
+    })
+
+    it('Buy with a proof of another leaf', async () => {
+        try {
+            await buy(amountToBuy, { allocation: amountToBuy + 1, proof: [] })
+            expect.fail('The leaf has to be in the tree')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.NotInAllowlist)
+        }
+    })
+
+    it('Buy with the allowlist proof', async function () {
+        if (!allowlistProof) {
+            this.skip()
+        }
+        const allowlistBefore = await getPresaleAllowlistAccountData(clientWallet.publicKey)
+        const txSig = await buy(amountToBuy, allowlistProof)
+        console.log('Your transaction signature', txSig)
+
+        const allowlistAfter = await getPresaleAllowlistAccountData(clientWallet.publicKey)
+        expect(allowlistAfter?.purchasedAmount).to.be.equal(
+            (allowlistBefore?.purchasedAmount ?? 0) + amountToBuy
+        )
+    })
+
+    it('Buy above the allocation', async function () {
+        // a zero allocation sets no limit
+        if (!allowlistProof?.allocation) {
+            this.skip()
+        }
+        try {
+            await buy(1, allowlistProof)
+            expect.fail('The allocation is used up')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.ExceedsTheAllowlistAllocation)
+        }
+    })
+})
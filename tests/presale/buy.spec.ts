@@ -10,6 +10,9 @@ import {
 
 const buyWithSol = 1_000_000
 const buyWithUsdc = 88_500_000
+// slippage caps of the payment, SOL and USDC
+const maxSolPayment = 100
+const maxUsdcPayment = 10_000
 
 describe('Presale Buy', () => {
     it('Buy with SOL', async () => {
@@ -19,7 +22,8 @@ describe('Presale Buy', () => {
                 clientWallet.publicKey,
                 buyWithSol,
                 Currency.SOL,
-                PresaleType.SixMonthsLockup
+                PresaleType.SixMonthsLockup,
+                maxSolPayment
             )
             const txSig = await signAndSendTransaction(transaction, [], clientWallet)
             console.log('Your transaction signature', txSig)
@@ -28,6 +32,7 @@ describe('Presale Buy', () => {
             result =
                 error.message === ErrorCode.InsufficientBalance ||
                 error.message === ErrorCode.ExceedsTheRemainingAmount ||
+                error.message === ErrorCode.ExceedsTheMaximumPayment ||
                 error.message === ErrorCode.ExceedsTheMaximumContribution
         }
         expect(result).to.be.equal(true)
@@ -39,7 +44,8 @@ describe('Presale Buy', () => {
                 clientWallet.publicKey,
                 buyWithUsdc,
                 Currency.USDC,
-                PresaleType.ThreeMonthsLockup
+                PresaleType.ThreeMonthsLockup,
+                maxUsdcPayment
             )
             const txSig = await signAndSendTransaction(transaction, [], clientWallet)
             console.log('Your transaction signature', txSig)
@@ -48,6 +54,7 @@ describe('Presale Buy', () => {
             result =
                 error.message === ErrorCode.InsufficientBalance ||
                 error.message === ErrorCode.ExceedsTheRemainingAmount ||
+                error.message === ErrorCode.ExceedsTheMaximumPayment ||
                 error.message === ErrorCode.ExceedsTheMaximumContribution
         }
         expect(result).to.be.equal(true)
@@ -0,0 +1,116 @@
+import { expect } from 'chai'
+import { clientWallet } from '../common'
+import {
+    ErrorCode,
+    PresaleType,
+    createTokenizePresalePositionTransaction,
+    getPresalePurchaseAccountData,
+    getPresaleReceiptSummaryAccountData,
+    getPresaleStatus,
+    getUserPresaleReceiptBalance,
+    signAndSendTransaction
+} from '../../app'
+
+// the USDC position of the buy spec
+const presaleType = PresaleType.ThreeMonthsLockup
+const amountToTokenize = 1_000
+
+const tokenize = async (amount: number) => {
+    const transaction = await createTokenizePresalePositionTransaction(
+        clientWallet.publicKey,
+        presaleType,
+        amount
+    )
+    return await signAndSendTransaction(transaction, [], clientWallet)
+}
+
+describe('Presale Receipt', () => {
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+    let purchase: Awaited<ReturnType<typeof getPresalePurchaseAccountData>>
+
+    before(async function () {
+        status = await getPresaleStatus()
+        expect(status).to.not.be.equal(null)
+        // the phase checks run after the position is loaded, so they need one
+        purchase = await getPresalePurchaseAccountData(clientWallet.publicKey, presaleType)
+        if (!purchase) {
+            this.skip()
+        }
+    })
+
+    it('Tokenize before the presale end', async function () {
+        if (status?.isEnded) {
+            this.skip()
+        }
+        try {
+            await tokenize(amountToTokenize)
+            expect.fail('Only final positions are tokenized')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleIsNotEnded)
+        }
+    })
+
+    it('Tokenize after the soft cap is missed', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await tokenize(amountToTokenize)
+            expect.fail('Receipts can not be refunded')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.SoftCapNotReached)
+        }
+    })
+
+    it('Tokenize nothing', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await tokenize(0)
+            expect.fail('A receipt needs an amount')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InsufficientBalance)
+        }
+    })
+
+    it('Tokenize more than the unclaimed amount', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await tokenize(purchase!.amount - purchase!.claimedAmount + 1)
+            expect.fail('Only the unclaimed part is tokenized')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InsufficientBalance)
+        }
+    })
+
+    it('Tokenize a part of the position', async function () {
+        if (
+            !status?.isEnded ||
+            !status.isSoftCapReached ||
+            purchase!.amount - purchase!.claimedAmount < amountToTokenize
+        ) {
+            this.skip()
+        }
+        const [receiptBefore, summaryBefore] = await Promise.all([
+            getUserPresaleReceiptBalance(clientWallet.publicKey, presaleType),
+            getPresaleReceiptSummaryAccountData(presaleType)
+        ])
+        const txSig = await tokenize(amountToTokenize)
+        console.log('Your transaction signature', txSig)
+        const [receiptAfter, summaryAfter, purchaseAfter] = await Promise.all([
+            getUserPresaleReceiptBalance(clientWallet.publicKey, presaleType),
+            getPresaleReceiptSummaryAccountData(presaleType),
+            getPresalePurchaseAccountData(clientWallet.publicKey, presaleType)
+        ])
+
+        // the receipts leave the position 1:1
+        expect(receiptAfter.amount - receiptBefore.amount).to.be.equal(amountToTokenize)
+        expect(purchaseAfter!.amount).to.be.closeTo(purchase!.amount - amountToTokenize, 1e-9)
+        expect(summaryAfter!.mintedAmount - (summaryBefore?.mintedAmount ?? 0)).to.be.equal(
+            amountToTokenize
+        )
+    })
+})
@@ -0,0 +1,171 @@
+import { expect } from 'chai'
+import * as anchor from '@coral-xyz/anchor'
+import { clientWallet, clientWallet2 } from '../common'
+import {
+    Currency,
+    ErrorCode,
+    PresaleType,
+    createBuyPresaleElwTransaction,
+    createClaimReferralRewardTransaction,
+    createRegisterReferrerTransaction,
+    getPresaleConfigAccountData,
+    getPresaleStatus,
+    getReferralAccountData,
+    getUserElwBalance,
+    signAndSendTransaction
+} from '../../app'
+
+const amountToBuy = 1_000_000
+const maxUsdcPayment = 10_000
+
+const register = async (referrer: anchor.Wallet) => {
+    const transaction = await createRegisterReferrerTransaction(referrer.publicKey)
+    return await signAndSendTransaction(transaction, [], referrer)
+}
+
+const buy = async (referrer: anchor.Wallet) => {
+    const transaction = await createBuyPresaleElwTransaction(
+        clientWallet.publicKey,
+        amountToBuy,
+        Currency.USDC,
+        PresaleType.ThreeMonthsLockup,
+        maxUsdcPayment,
+        null,
+        referrer.publicKey
+    )
+    return await signAndSendTransaction(transaction, [], clientWallet)
+}
+
+const claim = async () => {
+    const transaction = await createClaimReferralRewardTransaction(clientWallet2.publicKey)
+    return await signAndSendTransaction(transaction, [], clientWallet2)
+}
+
+// the client wallet buys, the second client wallet refers
+describe('Presale Referral', () => {
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+    let isBuyOpen: boolean
+
+    before(async () => {
+        status = await getPresaleStatus()
+        expect(status).to.not.be.equal(null)
+        // referred buys run in the public phase, the allowlist phase has its own spec
+        isBuyOpen = status!.isStarted && !status!.isEnded && !status!.isAllowlistPhase
+    })
+
+    it('Register a referrer', async function () {
+        if (await getReferralAccountData(clientWallet2.publicKey)) {
+            // registered by an earlier run
+            this.skip()
+        }
+        const txSig = await register(clientWallet2)
+        console.log('Your transaction signature', txSig)
+
+        const referral = await getReferralAccountData(clientWallet2.publicKey)
+        expect(referral?.referrer.toBase58()).to.be.equal(clientWallet2.publicKey.toBase58())
+        expect(referral?.purchaseCount).to.be.equal(0)
+        expect(referral?.earnedAmount).to.be.equal(0)
+    })
+
+    it('Register a referrer twice', async () => {
+        try {
+            await register(clientWallet2)
+            expect.fail('A referrer registers once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PdaAlreadyInUse)
+        }
+    })
+
+    it('Buy with a referrer', async function () {
+        if (!isBuyOpen) {
+            this.skip()
+        }
+        const [config, referralBefore] = await Promise.all([
+            getPresaleConfigAccountData(),
+            getReferralAccountData(clientWallet2.publicKey)
+        ])
+        const txSig = await buy(clientWallet2)
+        console.log('Your transaction signature', txSig)
+
+        // the bonus is paid on top of the sale, the buyer keeps the full amount
+        const referralAfter = await getReferralAccountData(clientWallet2.publicKey)
+        const bonus = (amountToBuy * config!.referralBonusPercentage) / 10_000
+        expect(referralAfter!.purchaseCount).to.be.equal(referralBefore!.purchaseCount + 1)
+        expect(referralAfter!.tokenVolume - referralBefore!.tokenVolume).to.be.equal(amountToBuy)
+        expect(referralAfter!.earnedAmount - referralBefore!.earnedAmount).to.be.closeTo(
+            bonus,
+            1e-9
+        )
+    })
+
+    it('Buy with the own referral', async function () {
+        if (!isBuyOpen) {
+            this.skip()
+        }
+        if (!(await getReferralAccountData(clientWallet.publicKey))) {
+            await register(clientWallet)
+        }
+        try {
+            await buy(clientWallet)
+            expect.fail('Buyers do not refer themselves')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidReferrer)
+        }
+    })
+
+    it('Claim before the presale end', async function () {
+        if (status?.isEnded) {
+            this.skip()
+        }
+        try {
+            await claim()
+            expect.fail('Bonuses are paid after the presale end')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleIsNotEnded)
+        }
+    })
+
+    it('Claim after the soft cap is missed', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await claim()
+            expect.fail('Bonuses are void when buyers are refunded')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.SoftCapNotReached)
+        }
+    })
+
+    it('Claim the referral reward', async function () {
+        const referralBefore = await getReferralAccountData(clientWallet2.publicKey)
+        if (!status?.isEnded || !status.isSoftCapReached || !referralBefore?.claimableAmount) {
+            this.skip()
+        }
+        const elwBefore = await getUserElwBalance(clientWallet2.publicKey)
+        const txSig = await claim()
+        console.log('Your transaction signature', txSig)
+        const [elwAfter, referralAfter] = await Promise.all([
+            getUserElwBalance(clientWallet2.publicKey),
+            getReferralAccountData(clientWallet2.publicKey)
+        ])
+
+        expect(elwAfter.amount - elwBefore.amount).to.be.closeTo(
+            referralBefore!.claimableAmount,
+            1e-9
+        )
+        expect(referralAfter?.claimedAmount).to.be.equal(referralAfter?.earnedAmount)
+    })
+
+    it('Claim with nothing left', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await claim()
+            expect.fail('The earned bonus is claimed once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.NoClaimableRewards)
+        }
+    })
+})
@@ -0,0 +1,210 @@
+import { expect } from 'chai'
+import { clientWallet, getArg } from '../common'
+import {
+    ErrorCode,
+    PresaleType,
+    createRefundPresaleTokenTransaction,
+    createRefundPresaleTransaction,
+    getPresalePaymentAccountData,
+    getPresalePaymentMintAccountData,
+    getPresalePurchaseAccountData,
+    getPresaleStatus,
+    getPresaleSummaryAccountData,
+    getUserSolBalance,
+    getUserUsdcBalance,
+    signAndSendTransaction
+} from '../../app'
+
+// positions of the buy spec, USDC with 3 months and SOL with 6 months lockup
+const presaleTypes = [PresaleType.ThreeMonthsLockup, PresaleType.SixMonthsLockup]
+
+const refund = async (presaleType: PresaleType) => {
+    const transaction = await createRefundPresaleTransaction(clientWallet.publicKey, presaleType)
+    return await signAndSendTransaction(transaction, [], clientWallet)
+}
+
+describe('Presale Refund', () => {
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+    let hasPosition: boolean
+
+    before(async () => {
+        status = await getPresaleStatus()
+        expect(status).to.not.be.equal(null)
+        // the phase checks run after the position is loaded, so they need one
+        hasPosition = !!(await getPresalePurchaseAccountData(
+            clientWallet.publicKey,
+            PresaleType.ThreeMonthsLockup
+        ))
+    })
+
+    it('Refund before the presale end', async function () {
+        if (status?.isEnded || !hasPosition) {
+            this.skip()
+        }
+        try {
+            await refund(PresaleType.ThreeMonthsLockup)
+            expect.fail('Refunds open after the presale end')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleIsNotEnded)
+        }
+    })
+
+    it('Refund after the soft cap is reached', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached || !hasPosition) {
+            this.skip()
+        }
+        try {
+            await refund(PresaleType.ThreeMonthsLockup)
+            expect.fail('A successful presale does not refund')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.SoftCapReached)
+        }
+    })
+
+    for (const presaleType of presaleTypes) {
+        it(`Refund the ${presaleType} position after the soft cap is missed`, async function () {
+            if (!status?.isEnded || status.isSoftCapReached) {
+                this.skip()
+            }
+            const purchase = await getPresalePurchaseAccountData(
+                clientWallet.publicKey,
+                presaleType
+            )
+            if (!purchase) {
+                // refunded by an earlier run
+                this.skip()
+            }
+
+            const [summaryBefore, solBefore, usdcBefore] = await Promise.all([
+                getPresaleSummaryAccountData(),
+                getUserSolBalance(clientWallet.publicKey),
+                getUserUsdcBalance(clientWallet.publicKey)
+            ])
+            const txSig = await refund(presaleType)
+            console.log('Your transaction signature', txSig)
+            const [summaryAfter, solAfter, usdcAfter] = await Promise.all([
+                getPresaleSummaryAccountData(),
+                getUserSolBalance(clientWallet.publicKey),
+                getUserUsdcBalance(clientWallet.publicKey)
+            ])
+
+            // the payment comes back and the position is cancelled
+            expect(usdcAfter.amount - usdcBefore.amount).to.be.closeTo(purchase!.usdcPaid, 1e-6)
+            // the closed position returns its rent on top, the fee is paid from it
+            expect(solAfter.amount - solBefore.amount).to.be.greaterThan(purchase!.solPaid)
+            expect(summaryAfter!.solRefunded - summaryBefore!.solRefunded).to.be.closeTo(
+                purchase!.solPaid,
+                1e-9
+            )
+            expect(summaryAfter!.usdcRefunded - summaryBefore!.usdcRefunded).to.be.closeTo(
+                purchase!.usdcPaid,
+                1e-6
+            )
+            expect(
+                await getPresalePurchaseAccountData(clientWallet.publicKey, presaleType)
+            ).to.be.equal(null)
+        })
+    }
+
+    it('Refund a cancelled position', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await refund(PresaleType.ThreeMonthsLockup)
+            expect.fail('A position is refunded once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.AccountNotInitialized)
+        }
+    })
+})
+
+describe('Presale Token Refund', () => {
+    // registered payment mint of the environment, the token buys run outside of the specs
+    const paymentMint = getArg('paymentMint')
+    const presaleType = PresaleType.ThreeMonthsLockup
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+    let decimals: number
+
+    const getPayment = async () =>
+        await getPresalePaymentAccountData(
+            clientWallet.publicKey,
+            paymentMint,
+            presaleType,
+            decimals
+        )
+
+    const refundToken = async () => {
+        const transaction = await createRefundPresaleTokenTransaction(
+            clientWallet.publicKey,
+            paymentMint,
+            presaleType
+        )
+        return await signAndSendTransaction(transaction, [], clientWallet)
+    }
+
+    before(async function () {
+        if (!paymentMint) {
+            this.skip()
+        }
+        status = await getPresaleStatus()
+        decimals = (await getPresalePaymentMintAccountData(paymentMint))!.decimals
+    })
+
+    it('Refund a token payment before the presale end', async function () {
+        if (status?.isEnded || !(await getPayment())) {
+            this.skip()
+        }
+        try {
+            await refundToken()
+            expect.fail('Refunds open after the presale end')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleIsNotEnded)
+        }
+    })
+
+    it('Refund a token payment after the soft cap is missed', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        const mintBefore = await getPresalePaymentMintAccountData(paymentMint)
+        const payment = await getPayment()
+        if (!payment) {
+            this.skip()
+        }
+        const purchaseBefore = await getPresalePurchaseAccountData(
+            clientWallet.publicKey,
+            presaleType
+        )
+
+        const txSig = await refundToken()
+        console.log('Your transaction signature', txSig)
+
+        // the payment account is closed and the ELW bought with it leaves the position
+        const mintAfter = await getPresalePaymentMintAccountData(paymentMint)
+        expect(mintAfter!.refunded - mintBefore!.refunded).to.be.closeTo(payment!.amount, 1e-6)
+        expect(await getPayment()).to.be.equal(null)
+        const purchaseAfter = await getPresalePurchaseAccountData(
+            clientWallet.publicKey,
+            presaleType
+        )
+        if (purchaseAfter) {
+            expect(purchaseAfter.amount).to.be.closeTo(
+                (purchaseBefore?.amount ?? 0) - payment!.tokenBought,
+                1e-9
+            )
+        }
+    })
+
+    it('Refund a token payment twice', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await refundToken()
+            expect.fail('A payment is refunded once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.AccountNotInitialized)
+        }
+    })
+})
@@ -0,0 +1,159 @@
+import { expect } from 'chai'
+import { getArg } from '../common'
+import ElowenProgram, {
+    ErrorCode,
+    VaultAccount,
+    createReleasePresaleFundsTransaction,
+    createReleasePresaleTokenFundsTransaction,
+    getEdaVaultBalances,
+    getPresalePaymentMintAccountData,
+    getPresaleStatus,
+    getPresaleSummaryAccountData,
+    getUserSolBalance,
+    getVaultAccount,
+    signAndSendTransaction
+} from '../../app'
+
+// permissionless, the platform wallet pays for the missing vault accounts
+const release = async () => {
+    const transaction = await createReleasePresaleFundsTransaction(ElowenProgram.wallet.publicKey)
+    return await signAndSendTransaction(transaction)
+}
+
+describe('Presale Release', () => {
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+
+    before(async () => {
+        status = await getPresaleStatus()
+        expect(status).to.not.be.equal(null)
+    })
+
+    it('Release before the presale end', async function () {
+        if (status?.isEnded) {
+            this.skip()
+        }
+        try {
+            await release()
+            expect.fail('Funds stay in the escrow until the presale end')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleIsNotEnded)
+        }
+    })
+
+    it('Release after the soft cap is missed', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await release()
+            expect.fail('Funds of a failed presale are refunded')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.SoftCapNotReached)
+        }
+    })
+
+    it('Release the raised funds', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached || status.isFundsReleased) {
+            this.skip()
+        }
+        const edaVault = getVaultAccount(VaultAccount.Eda)
+        const [edaSolBefore, edaBefore] = await Promise.all([
+            getUserSolBalance(edaVault),
+            getEdaVaultBalances()
+        ])
+        const txSig = await release()
+        console.log('Your transaction signature', txSig)
+        const [edaSolAfter, edaAfter, summary] = await Promise.all([
+            getUserSolBalance(edaVault),
+            getEdaVaultBalances(),
+            getPresaleSummaryAccountData()
+        ])
+
+        // everything raised is split between the EDA and the liquidity
+        expect(summary?.isFundsReleased).to.be.equal(true)
+        expect(summary!.solSentToEda + summary!.solSentToLiquidity).to.be.closeTo(
+            summary!.solRaised,
+            1e-9
+        )
+        expect(summary!.usdcSentToEda + summary!.usdcSentToLiquidity).to.be.closeTo(
+            summary!.usdcRaised,
+            1e-6
+        )
+        expect(edaSolAfter.amount - edaSolBefore.amount).to.be.closeTo(summary!.solSentToEda, 1e-9)
+        expect((edaAfter?.usdcAmount ?? 0) - (edaBefore?.usdcAmount ?? 0)).to.be.closeTo(
+            summary!.usdcSentToEda,
+            1e-6
+        )
+    })
+
+    it('Release twice', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await release()
+            expect.fail('Funds are released once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleFundsAlreadyReleased)
+        }
+    })
+})
+
+describe('Presale Token Release', () => {
+    // registered payment mint of the environment
+    const paymentMint = getArg('paymentMint')
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+
+    const releaseToken = async () => {
+        const transaction = await createReleasePresaleTokenFundsTransaction(
+            ElowenProgram.wallet.publicKey,
+            paymentMint
+        )
+        return await signAndSendTransaction(transaction)
+    }
+
+    before(async function () {
+        if (!paymentMint) {
+            this.skip()
+        }
+        status = await getPresaleStatus()
+    })
+
+    it('Release the token funds after the soft cap is missed', async function () {
+        if (!status?.isEnded || status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await releaseToken()
+            expect.fail('Funds of a failed presale are refunded')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.SoftCapNotReached)
+        }
+    })
+
+    it('Release the token funds', async function () {
+        const paymentMintBefore = await getPresalePaymentMintAccountData(paymentMint)
+        if (!status?.isEnded || !status.isSoftCapReached || paymentMintBefore?.isFundsReleased) {
+            this.skip()
+        }
+        const txSig = await releaseToken()
+        console.log('Your transaction signature', txSig)
+
+        // registered mints have no pool, the whole raise goes to the EDA
+        const paymentMintAfter = await getPresalePaymentMintAccountData(paymentMint)
+        expect(paymentMintAfter?.isFundsReleased).to.be.equal(true)
+        expect(paymentMintAfter?.sentToEda).to.be.equal(paymentMintAfter?.raised)
+    })
+
+    it('Release the token funds twice', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached) {
+            this.skip()
+        }
+        try {
+            await releaseToken()
+            expect.fail('Funds are released once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleFundsAlreadyReleased)
+        }
+    })
+})
@@ -0,0 +1,116 @@
+import { expect } from 'chai'
+import * as anchor from '@coral-xyz/anchor'
+import { clientWallet, clientWallet2 } from '../common'
+import {
+    ErrorCode,
+    PresaleType,
+    createTransferPresalePositionTransaction,
+    getPresalePurchaseAccountData,
+    getPresaleStatus,
+    signAndSendTransaction
+} from '../../app'
+
+// the SOL position of the buy spec, the USDC one is left to the claim and receipt specs
+const presaleType = PresaleType.SixMonthsLockup
+
+const transfer = async (newOwner: anchor.Wallet) => {
+    const transaction = await createTransferPresalePositionTransaction(
+        clientWallet.publicKey,
+        newOwner.publicKey,
+        presaleType
+    )
+    return await signAndSendTransaction(transaction, [], clientWallet)
+}
+
+describe('Presale Position Transfer', () => {
+    let status: Awaited<ReturnType<typeof getPresaleStatus>>
+    let hasPosition: boolean
+
+    before(async () => {
+        status = await getPresaleStatus()
+        expect(status).to.not.be.equal(null)
+        // the checks run after the position is loaded, so they need one
+        hasPosition = !!(await getPresalePurchaseAccountData(clientWallet.publicKey, presaleType))
+    })
+
+    it('Transfer to the own wallet', async function () {
+        if (!hasPosition) {
+            this.skip()
+        }
+        try {
+            await transfer(clientWallet)
+            expect.fail('The new owner has to be another wallet')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidReceiver)
+        }
+    })
+
+    it('Transfer before the presale end', async function () {
+        if (status?.isEnded || !hasPosition) {
+            this.skip()
+        }
+        try {
+            await transfer(clientWallet2)
+            expect.fail('Only final positions move')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PresaleIsNotEnded)
+        }
+    })
+
+    it('Transfer after the soft cap is missed', async function () {
+        if (!status?.isEnded || status.isSoftCapReached || !hasPosition) {
+            this.skip()
+        }
+        try {
+            await transfer(clientWallet2)
+            expect.fail('Refundable positions do not move')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.SoftCapNotReached)
+        }
+    })
+
+    it('Transfer the position', async function () {
+        const ownerBefore = await getPresalePurchaseAccountData(clientWallet.publicKey, presaleType)
+        if (!status?.isEnded || !status.isSoftCapReached || !ownerBefore || ownerBefore.claimed) {
+            this.skip()
+        }
+        const newOwnerBefore = await getPresalePurchaseAccountData(
+            clientWallet2.publicKey,
+            presaleType
+        )
+        const txSig = await transfer(clientWallet2)
+        console.log('Your transaction signature', txSig)
+        const [ownerAfter, newOwnerAfter] = await Promise.all([
+            getPresalePurchaseAccountData(clientWallet.publicKey, presaleType),
+            getPresalePurchaseAccountData(clientWallet2.publicKey, presaleType)
+        ])
+
+        // the lockup follows the position
+        expect(ownerAfter).to.be.equal(null)
+        expect(newOwnerAfter!.amount).to.be.closeTo(
+            (newOwnerBefore?.amount ?? 0) + ownerBefore!.amount,
+            1e-9
+        )
+        expect(newOwnerAfter!.claimedAmount).to.be.closeTo(
+            (newOwnerBefore?.claimedAmount ?? 0) + ownerBefore!.claimedAmount,
+            1e-9
+        )
+        expect(newOwnerAfter!.unlockTime).to.be.equal(ownerBefore!.unlockTime)
+    })
+
+    it('Transfer a moved position', async function () {
+        if (!status?.isEnded || !status.isSoftCapReached) {
+            this.skip()
+        }
+        if (await getPresalePurchaseAccountData(clientWallet.publicKey, presaleType)) {
+            // claimed before it could move
+            this.skip()
+        }
+        try {
+            await transfer(clientWallet2)
+            expect.fail('The owner account is closed by the transfer')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.AccountNotInitialized)
+        }
+    })
+})
@@ -1,164 +1,131 @@
 import { expect } from 'chai'
-import { clientWallet } from '../common'
-import { Wallet } from '@coral-xyz/anchor'
+import { buildRewardTree } from './merkle'
+import { clientWallet, clientWallet2 } from '../common'
 import ElowenProgram, {
     ErrorCode,
-    VaultAccount,
     createClaimElwRewardTransaction,
-    getRewardAccountData,
-    getVaultAccount,
+    createPublishRewardEpochTransaction,
+    getUserRewardAccountData,
     signAndSendTransaction
 } from '../../app'
 
-const initialTime = 1765756800
+// first month after the presale, in the first reward pool
+const pool = 0
+const epoch = 0
 
-const getMonthLaterTimestamp = (month: number): number => {
-    const date = new Date(initialTime * 1000)
-    date.setUTCMonth(date.getUTCMonth() + month)
-    return Math.floor(date.getTime() / 1000)
-}
-
-const claimableRewards = [
-    {
-        timestamp: getMonthLaterTimestamp(0), // 62500000
-        percentage: 2 // 2% of 62500000 = 1250000
-    },
-    {
-        timestamp: getMonthLaterTimestamp(1), // 62500000
-        percentage: 5 // 5% of 62500000 = 3125000
-    },
-    {
-        timestamp: getMonthLaterTimestamp(2), // 62500000
-        percentage: 8 // 8% of 62500000 = 5000000
-    },
-    {
-        timestamp: getMonthLaterTimestamp(3), // 62500000
-        percentage: 10 // 10% of 62500000 = 6250000
-    },
-    {
-        timestamp: getMonthLaterTimestamp(4), // 31250000
-        percentage: 2 // 2% of 31250000 = 625000
-    },
-    {
-        timestamp: getMonthLaterTimestamp(5), // 31250000
-        percentage: 6 // 6% of 31250000 = 1875000
-    },
-    {
-        timestamp: getMonthLaterTimestamp(6), // 31250000
-        percentage: 7 // 7% of 31250000 = 2187500
-    },
-    {
-        timestamp: getMonthLaterTimestamp(7), // 31250000
-        percentage: 3 // 3% of 31250000 = 937500
-    },
-    {
-        timestamp: getMonthLaterTimestamp(8), // 15625000
-        percentage: 1 // 1% of 15625000 = 156250
-    },
-    {
-        timestamp: getMonthLaterTimestamp(9), // 15625000
-        percentage: 7 // 7% of 15625000 = 1093750
-    },
-    {
-        timestamp: getMonthLaterTimestamp(10), // 15625000
-        percentage: 3 // 3% of 15625000 = 468750
-    },
-    {
-        timestamp: getMonthLaterTimestamp(11), // 15625000
-        percentage: 12 // 12% of 15625000 = 1875000
-    }
-]
-
-// total distributed = (62500000 * 4) + (31250000 * 4) + (15625000 * 4) = 437500000
-
-// total reward first 6 month = 1250000 + 3125000 + 5000000 + 6250000 + 625000 + 1875000 = 18125000
-// total reward last 6 month = 2187500 + 937500 + 156250 + 1093750 + 468750 + 1875000 = 6718750
-
-// total reward = 18125000 + 6718750 = 24843750
-// percentage = 24843750 / 500000000 * 100 = 4.97
-
-const initialReward = 24843750
-const initialPercentage = 4.97
+const rewardTree = buildRewardTree(pool, epoch, [
+    { receiver: clientWallet.publicKey, amount: 1_250_000 },
+    { receiver: clientWallet2.publicKey, amount: 3_125_000 }
+])
 
 describe('Claim rewards', () => {
-    // test for unauthorized access
-    afterEach(() => {
-        // @ts-ignore - for test
-        ElowenProgram._provider.wallet = Wallet.local()
+    // platform wallet holds the reward distributor role
+    it('Publish a reward epoch', async () => {
+        let result: any
+        try {
+            const transaction = await createPublishRewardEpochTransaction(
+                ElowenProgram.wallet.publicKey,
+                pool,
+                epoch,
+                rewardTree.root,
+                rewardTree.totalAmount,
+                2
+            )
+            const txSig = await signAndSendTransaction(transaction)
+            console.log('Your transaction signature', txSig)
+            result = true
+        } catch (error: any) {
+            result =
+                error.message === ErrorCode.PdaAlreadyInUse ||
+                error.message === ErrorCode.PresaleIsNotEnded ||
+                error.message === ErrorCode.ClaimableRewardNotReady
+        }
+        expect(result).to.be.equal(true)
     })
-    it('Withdraw bulk reward without platform signer (partialSign)', async () => {
+
+    it('Publish a reward epoch without the distributor role', async () => {
         try {
-            const claimableRewards = [
-                {
-                    timestamp: getMonthLaterTimestamp(0),
-                    percentage: 2
-                }
-            ]
-            // @ts-ignore - for test
-            ElowenProgram._provider.wallet = clientWallet
-            const { transaction } = await createClaimElwRewardTransaction(
+            const transaction = await createPublishRewardEpochTransaction(
                 clientWallet.publicKey,
-                claimableRewards
+                pool,
+                epoch,
+                rewardTree.root,
+                rewardTree.totalAmount,
+                2
             )
-            const txSig = await signAndSendTransaction(transaction, [], clientWallet)
-            console.log('Your transaction signature', txSig)
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Publishing without the role should fail')
         } catch (error: any) {
             expect(error.message).to.be.equal(ErrorCode.Unauthorized)
         }
     })
 
-    // test for client access also signer have to be our wallet
-    it('Withdraw bulk reward as a client', async () => {
-        let result: any
+    it('Claim a reward with a wrong amount', async () => {
         try {
-            const { transaction, signerWallet } = await createClaimElwRewardTransaction(
+            const transaction = await createClaimElwRewardTransaction(
                 clientWallet.publicKey,
-                claimableRewards
-            )
-            const rewardAccountBefore = await getRewardAccountData(clientWallet.publicKey)
-            const txSig = await signAndSendTransaction(transaction, [signerWallet], clientWallet)
-            const rewardAccountAfter = await getRewardAccountData(clientWallet.publicKey)
-            expect(rewardAccountAfter?.amount).to.be.equal(
-                (rewardAccountBefore?.amount ?? 0) + initialReward
-            )
-            expect(rewardAccountAfter?.percentage).to.be.equal(
-                (rewardAccountBefore?.percentage ?? 0) + initialPercentage
+                clientWallet.publicKey,
+                { ...rewardTree.getClaimableReward(0), amount: 2_500_000 }
             )
-            expect(rewardAccountAfter?.receiver.toBase58()).to.be.equal(
-                clientWallet.publicKey.toBase58()
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('A wrong amount should not match the proof')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidMerkleProof)
+        }
+    })
+
+    it('Claim a reward with the proof of another receiver', async () => {
+        try {
+            const transaction = await createClaimElwRewardTransaction(
+                clientWallet.publicKey,
+                clientWallet.publicKey,
+                rewardTree.getClaimableReward(1)
             )
-            console.log('Your transaction signature', txSig)
-            result = true
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('A proof of another receiver should not match')
         } catch (error: any) {
-            result = error.message === ErrorCode.ClaimableRewardNotReady
+            expect(error.message).to.be.equal(ErrorCode.InvalidMerkleProof)
         }
-        expect(result).to.be.equal(true)
     })
 
-    // test for platform access, signer have to be same receiver wallet
-    it('Withdraw bulk reward as a platform', async () => {
+    // claims are permissionless, the platform pays for the client here
+    it('Claim a reward for a client', async () => {
         let result: any
         try {
-            const platformPda = getVaultAccount(VaultAccount.Platform)
-            const { transaction, signerWallet } = await createClaimElwRewardTransaction(
+            const claimableReward = rewardTree.getClaimableReward(0)
+            const transaction = await createClaimElwRewardTransaction(
                 ElowenProgram.wallet.publicKey,
-                claimableRewards
+                clientWallet.publicKey,
+                claimableReward
             )
-            const rewardAccountBefore = await getRewardAccountData(platformPda)
-            const txSig = await signAndSendTransaction(transaction, [signerWallet])
-            const rewardAccountAfter = await getRewardAccountData(platformPda)
-            expect(rewardAccountAfter?.amount).to.be.equal(
-                (rewardAccountBefore?.amount ?? 0) + initialReward
+            const rewardAccountBefore = await getUserRewardAccountData(clientWallet.publicKey, pool)
+            const txSig = await signAndSendTransaction(transaction)
+            const rewardAccountAfter = await getUserRewardAccountData(clientWallet.publicKey, pool)
+            expect(rewardAccountAfter?.claimedAmount).to.be.equal(
+                (rewardAccountBefore?.claimedAmount ?? 0) + claimableReward.amount
             )
-            expect(rewardAccountAfter?.percentage).to.be.equal(
-                (rewardAccountBefore?.percentage ?? 0) + initialPercentage
+            expect(rewardAccountAfter?.receiver.toBase58()).to.be.equal(
+                clientWallet.publicKey.toBase58()
             )
-            expect(rewardAccountAfter?.receiver.toBase58()).to.be.equal(platformPda.toBase58())
             console.log('Your transaction signature', txSig)
             result = true
         } catch (error: any) {
-            result = error.message === ErrorCode.ClaimableRewardNotReady
+            result = error.message === ErrorCode.AlreadyClaimedForThisPeriod
         }
         expect(result).to.be.equal(true)
     })
+
+    it('Claim the same reward twice', async () => {
+        try {
+            const transaction = await createClaimElwRewardTransaction(
+                clientWallet.publicKey,
+                clientWallet.publicKey,
+                rewardTree.getClaimableReward(0)
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('A reward should only be claimed once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.AlreadyClaimedForThisPeriod)
+        }
+    })
 })
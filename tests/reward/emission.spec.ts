@@ -0,0 +1,128 @@
+import { expect } from 'chai'
+import { clientWallet } from '../common'
+import { executeMultisigTransaction } from '../multisig'
+import {
+    EmissionCurve,
+    EmissionSchedule,
+    ErrorCode,
+    createInitializeEmissionScheduleTransaction,
+    createUpdateEmissionScheduleTransaction,
+    getEmission,
+    getEmissionScheduleAccountData,
+    getPresaleConfigAccountData,
+    signAndSendTransaction
+} from '../../app'
+
+const halvingSchedule: EmissionSchedule = {
+    curve: EmissionCurve.Halving,
+    baseAmount: 1_000_000,
+    halvingMonths: 12,
+    decayAmount: 0,
+    minAmount: 0,
+    steps: []
+}
+
+const stepsSchedule: EmissionSchedule = {
+    ...halvingSchedule,
+    curve: EmissionCurve.Steps,
+    steps: [
+        { fromMonth: 0, amount: 500_000 },
+        { fromMonth: 12, amount: 250_000 }
+    ]
+}
+
+// months are calendar months, a few days on top keep a leap year out of the way
+const twoYears = 750 * 24 * 60 * 60
+
+describe('Emission Schedule', () => {
+    let presaleEndTime: number
+
+    before(async () => {
+        presaleEndTime = (await getPresaleConfigAccountData())!.endTime
+        if (!(await getEmissionScheduleAccountData())) {
+            await executeMultisigTransaction(
+                'test emission schedule initialize',
+                await createInitializeEmissionScheduleTransaction(halvingSchedule)
+            )
+        }
+    })
+
+    after(async () => {
+        // the reward specs expect the halving schedule
+        await executeMultisigTransaction(
+            'test emission schedule restore',
+            await createUpdateEmissionScheduleTransaction(halvingSchedule)
+        )
+    })
+
+    it('Initialize the schedule twice', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test emission schedule initialize twice',
+                await createInitializeEmissionScheduleTransaction(halvingSchedule)
+            )
+            expect.fail('The schedule is initialized once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PdaAlreadyInUse)
+        }
+    })
+
+    it('Update the schedule without the owner', async () => {
+        try {
+            const transaction = await createUpdateEmissionScheduleTransaction(
+                stepsSchedule,
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the owner updates the schedule')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+    })
+
+    it('Update to an invalid schedule', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test emission schedule invalid',
+                await createUpdateEmissionScheduleTransaction({
+                    ...halvingSchedule,
+                    halvingMonths: 0
+                })
+            )
+            expect.fail('A halving needs a period')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidEmissionSchedule)
+        }
+    })
+
+    it('Update to custom steps', async () => {
+        const txSig = await executeMultisigTransaction(
+            'test emission schedule steps',
+            await createUpdateEmissionScheduleTransaction(stepsSchedule)
+        )
+        console.log('Your transaction signature', txSig)
+
+        const schedule = await getEmissionScheduleAccountData()
+        expect(schedule?.curve).to.be.equal(EmissionCurve.Steps)
+        expect(schedule?.steps).to.be.deep.equal(stepsSchedule.steps)
+
+        // each step holds from its month on
+        expect(await getEmission(presaleEndTime)).to.be.equal(500_000)
+        expect(await getEmission(presaleEndTime + twoYears)).to.be.equal(250_000)
+    })
+
+    it('Emission before the presale end', async () => {
+        expect(await getEmission(presaleEndTime - 1)).to.be.equal(0)
+    })
+
+    it('Emission of the halving schedule', async () => {
+        await executeMultisigTransaction(
+            'test emission schedule halving',
+            await createUpdateEmissionScheduleTransaction(halvingSchedule)
+        )
+
+        // two halvings after two years
+        expect(await getEmission(presaleEndTime)).to.be.equal(1_000_000)
+        expect(await getEmission(presaleEndTime + twoYears)).to.be.equal(250_000)
+    })
+})
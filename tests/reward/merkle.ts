@@ -0,0 +1,56 @@
+import { PublicKey } from '@solana/web3.js'
+import { keccak_256 } from '@noble/hashes/sha3'
+import { ClaimableReward, toTokenFormat } from '../../app'
+
+export type RewardLeaf = {
+    receiver: PublicKey
+    amount: number
+}
+
+const keccak = (...buffers: Buffer[]) => Buffer.from(keccak_256(Buffer.concat(buffers)))
+
+// same leaf as the program, keccak(index le, receiver, amount le)
+const hashLeaf = (index: number, leaf: RewardLeaf) => {
+    const indexBuffer = Buffer.alloc(4)
+    indexBuffer.writeUInt32LE(index)
+    return keccak(
+        indexBuffer,
+        leaf.receiver.toBuffer(),
+        toTokenFormat(leaf.amount).toArrayLike(Buffer, 'le', 8)
+    )
+}
+
+// pairs are sorted before hashing, an odd node moves up unchanged
+const hashPair = (left: Buffer, right: Buffer) =>
+    Buffer.compare(left, right) <= 0 ? keccak(left, right) : keccak(right, left)
+
+export const buildRewardTree = (pool: number, epoch: number, leaves: RewardLeaf[]) => {
+    const levels = [leaves.map((leaf, index) => hashLeaf(index, leaf))]
+    while (levels[levels.length - 1].length > 1) {
+        const level = levels[levels.length - 1]
+        const nextLevel: Buffer[] = []
+        for (let i = 0; i < level.length; i += 2) {
+            nextLevel.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i])
+        }
+        levels.push(nextLevel)
+    }
+
+    const getClaimableReward = (index: number): ClaimableReward => {
+        const proof: Buffer[] = []
+        let position = index
+        for (const level of levels.slice(0, -1)) {
+            const sibling = position ^ 1
+            if (sibling < level.length) {
+                proof.push(level[sibling])
+            }
+            position >>= 1
+        }
+        return { pool, epoch, index, amount: leaves[index].amount, proof }
+    }
+
+    return {
+        root: levels[levels.length - 1][0],
+        totalAmount: leaves.reduce((total, leaf) => total + leaf.amount, 0),
+        getClaimableReward
+    }
+}
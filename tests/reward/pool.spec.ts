@@ -0,0 +1,163 @@
+import { expect } from 'chai'
+import { clientWallet } from '../common'
+import { executeMultisigTransaction } from '../multisig'
+import {
+    ErrorCode,
+    createCreateRewardPoolTransaction,
+    createUpdateRewardPoolTransaction,
+    getRewardPoolAccountData,
+    getRewardPoolsAccountData,
+    signAndSendTransaction
+} from '../../app'
+
+// the claim spec publishes in the first pool
+const pool = 0
+const sharePercentage = 5_000
+// pools are numbered below this limit
+const maxRewardPools = 8
+
+describe('Reward Pool', () => {
+    after(async () => {
+        // the claim spec expects the first pool enabled with its share
+        await executeMultisigTransaction(
+            'test reward pool restore',
+            await createUpdateRewardPoolTransaction(pool, sharePercentage, true)
+        )
+    })
+
+    it('Create a pool without the owner', async () => {
+        try {
+            const transaction = await createCreateRewardPoolTransaction(
+                maxRewardPools - 1,
+                sharePercentage,
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the owner creates pools')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+    })
+
+    it('Create the first pool', async function () {
+        if (await getRewardPoolAccountData(pool)) {
+            // created by an earlier run
+            this.skip()
+        }
+        const poolsBefore = await getRewardPoolsAccountData()
+        const txSig = await executeMultisigTransaction(
+            'test reward pool create',
+            await createCreateRewardPoolTransaction(pool, sharePercentage)
+        )
+        console.log('Your transaction signature', txSig)
+
+        const [rewardPool, poolsAfter] = await Promise.all([
+            getRewardPoolAccountData(pool),
+            getRewardPoolsAccountData()
+        ])
+        expect(rewardPool?.sharePercentage).to.be.equal(sharePercentage)
+        expect(rewardPool?.isEnabled).to.be.equal(true)
+        expect(poolsAfter?.totalSharePercentage).to.be.equal(
+            (poolsBefore?.totalSharePercentage ?? 0) + sharePercentage
+        )
+    })
+
+    it('Create a pool twice', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test reward pool create twice',
+                await createCreateRewardPoolTransaction(pool, sharePercentage)
+            )
+            expect.fail('A pool id is used once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PdaAlreadyInUse)
+        }
+    })
+
+    it('Create a pool above the pool limit', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test reward pool create above the limit',
+                await createCreateRewardPoolTransaction(maxRewardPools, 0)
+            )
+            expect.fail('The pool id is above the limit')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidRewardPool)
+        }
+    })
+
+    it('Create a pool above the whole emission', async function () {
+        if (await getRewardPoolAccountData(maxRewardPools - 1)) {
+            this.skip()
+        }
+        try {
+            await executeMultisigTransaction(
+                'test reward pool create above the emission',
+                await createCreateRewardPoolTransaction(maxRewardPools - 1, 10_001)
+            )
+            expect.fail('The shares are at most the whole emission')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.InvalidRewardPool)
+        }
+    })
+
+    it('Update a pool without the owner', async () => {
+        try {
+            const transaction = await createUpdateRewardPoolTransaction(
+                pool,
+                sharePercentage,
+                false,
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the owner updates pools')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.Unauthorized)
+        }
+    })
+
+    it('Update the pool share', async () => {
+        const [poolBefore, poolsBefore] = await Promise.all([
+            getRewardPoolAccountData(pool),
+            getRewardPoolsAccountData()
+        ])
+        const newSharePercentage = sharePercentage + 1_000
+        const txSig = await executeMultisigTransaction(
+            'test reward pool update',
+            await createUpdateRewardPoolTransaction(pool, newSharePercentage, true)
+        )
+        console.log('Your transaction signature', txSig)
+        const [poolAfter, poolsAfter] = await Promise.all([
+            getRewardPoolAccountData(pool),
+            getRewardPoolsAccountData()
+        ])
+
+        const enabledShareBefore = poolBefore!.isEnabled ? poolBefore!.sharePercentage : 0
+        expect(poolAfter?.sharePercentage).to.be.equal(newSharePercentage)
+        expect(poolsAfter!.totalSharePercentage).to.be.equal(
+            poolsBefore!.totalSharePercentage - enabledShareBefore + newSharePercentage
+        )
+    })
+
+    it('Disable the pool', async () => {
+        const [poolBefore, poolsBefore] = await Promise.all([
+            getRewardPoolAccountData(pool),
+            getRewardPoolsAccountData()
+        ])
+        const txSig = await executeMultisigTransaction(
+            'test reward pool disable',
+            await createUpdateRewardPoolTransaction(pool, poolBefore!.sharePercentage, false)
+        )
+        console.log('Your transaction signature', txSig)
+        const [poolAfter, poolsAfter] = await Promise.all([
+            getRewardPoolAccountData(pool),
+            getRewardPoolsAccountData()
+        ])
+
+        // only enabled pools hold a share of the emission
+        expect(poolAfter?.isEnabled).to.be.equal(false)
+        expect(poolsAfter!.totalSharePercentage).to.be.equal(
+            poolsBefore!.totalSharePercentage - poolBefore!.sharePercentage
+        )
+    })
+})
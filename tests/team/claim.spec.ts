@@ -1,76 +1,272 @@
 import { expect } from 'chai'
-import { clientWallet, clientWallet2, emptyWallet } from '../common'
-import { ErrorCode, createClaimTeamMemberElwTransaction, signAndSendTransaction } from '../../app'
-import { SendTransactionError } from '@solana/web3.js'
-import { PublicKey } from '@solana/web3.js'
+import * as anchor from '@coral-xyz/anchor'
+import { clientWallet } from '../common'
+import { createFundedWallet } from '../wallet'
+import { getAssociatedTokenAddressSync } from '@solana/spl-token'
+import { executeMultisigTransaction } from '../multisig'
+import {
+    ErrorCode,
+    VestingSchedule,
+    createClaimTeamMemberElwTransaction,
+    createCreateTeamVestingTransaction,
+    createRevokeTeamVestingTransaction,
+    getEdaVaultBalances,
+    getElwMint,
+    getTeamMemberClaimAccountData,
+    getTeamVaultElwBalance,
+    getTeamVestingAccountData,
+    getTokenAccountInfo,
+    getVaultAccountElwAta,
+    signAndSendTransaction,
+    VaultAccount
+} from '../../app'
 
-describe('Member Claim Token', () => {
-    it('Claim team elw as a wrong wallet', async () => {
+const totalAmount = 1000
+
+// streams over a long period, so a revoke always leaves unvested tokens
+const getStreamingSchedule = (): VestingSchedule => {
+    const now = Math.floor(Date.now() / 1000)
+    return { startTime: now - 600, cliffTime: now - 600, endTime: now + 86400, tgePercentage: 0 }
+}
+
+describe('Team Vesting', () => {
+    let member: anchor.Wallet
+
+    before(async () => {
+        member = await createFundedWallet()
+    })
+
+    it('Create a team vesting without the owner', async () => {
         try {
-            const transaction = await createClaimTeamMemberElwTransaction(emptyWallet.publicKey)
-            const txSig = await signAndSendTransaction(transaction, [], emptyWallet)
-            console.log('Your transaction signature', txSig)
+            const transaction = await createCreateTeamVestingTransaction(
+                member.publicKey,
+                totalAmount,
+                getStreamingSchedule(),
+                clientWallet.publicKey
+            )
+            await signAndSendTransaction(transaction, [], clientWallet)
+            expect.fail('Only the owner creates team vestings')
         } catch (error: any) {
             expect(error.message).to.be.equal(ErrorCode.Unauthorized)
         }
     })
 
-    it('Claim team elw as a team member 1', async () => {
-        let result: any
+    it('Create a team vesting', async () => {
+        const schedule = getStreamingSchedule()
+        const txSig = await executeMultisigTransaction(
+            'test team vesting create',
+            await createCreateTeamVestingTransaction(member.publicKey, totalAmount, schedule)
+        )
+        console.log('Your transaction signature', txSig)
+
+        const vesting = await getTeamVestingAccountData(member.publicKey)
+        expect(vesting?.member.toBase58()).to.be.equal(member.publicKey.toBase58())
+        expect(vesting?.totalAmount).to.be.equal(totalAmount)
+        expect(vesting?.isRevoked).to.be.equal(false)
+        expect(vesting?.schedule).to.be.deep.equal(schedule)
+    })
+
+    it('Create a team vesting while the current one is active', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test team vesting create twice',
+                await createCreateTeamVestingTransaction(
+                    member.publicKey,
+                    totalAmount,
+                    getStreamingSchedule()
+                )
+            )
+            expect.fail('An active vesting can not be replaced')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.VestingIsNotRevoked)
+        }
+    })
+
+    it('Revoke a team vesting', async () => {
+        const edaBefore = await getEdaVaultBalances()
+        const txSig = await executeMultisigTransaction(
+            'test team vesting revoke',
+            await createRevokeTeamVestingTransaction(member.publicKey)
+        )
+        console.log('Your transaction signature', txSig)
+        const edaAfter = await getEdaVaultBalances()
+
+        // the total is cut down to the vested amount, the rest moves to the EDA
+        const vesting = await getTeamVestingAccountData(member.publicKey)
+        expect(vesting?.isRevoked).to.be.equal(true)
+        expect(vesting?.totalAmount).to.be.greaterThan(0)
+        expect(vesting?.totalAmount).to.be.lessThan(totalAmount)
+        expect((edaAfter?.elwAmount ?? 0) - (edaBefore?.elwAmount ?? 0)).to.be.closeTo(
+            totalAmount - (vesting?.totalAmount ?? 0),
+            1e-6
+        )
+    })
+
+    it('Revoke a revoked team vesting', async () => {
+        try {
+            await executeMultisigTransaction(
+                'test team vesting revoke twice',
+                await createRevokeTeamVestingTransaction(member.publicKey)
+            )
+            expect.fail('A vesting is only revoked once')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.VestingIsRevoked)
+        }
+    })
+
+    it('Create a team vesting before the revoked one is claimed', async () => {
         try {
-            const transaction = await createClaimTeamMemberElwTransaction(
-                new PublicKey('9HGJSAC4HAwtQEpGSDNSWxDsksBVUFHVDUnu5JbhVwnK')
+            await executeMultisigTransaction(
+                'test team vesting recreate unclaimed',
+                await createCreateTeamVestingTransaction(
+                    member.publicKey,
+                    totalAmount,
+                    getStreamingSchedule()
+                )
             )
-            const txSig = await signAndSendTransaction(transaction)
-            console.log('Your transaction signature', txSig)
-            result = true
+            expect.fail('Vested tokens of the revoked vesting must be claimed first')
         } catch (error: any) {
-            if (error instanceof SendTransactionError) {
-                console.log(error.logs)
-            }
-            result =
-                error.message === ErrorCode.PeriodNotReached ||
-                error.message === ErrorCode.NotEnoughBalanceInVault ||
-                error.message === ErrorCode.AlreadyClaimedForThisPeriod
+            expect(error.message).to.be.equal(ErrorCode.VestedTokensNotClaimed)
         }
-        expect(result).to.be.equal(true)
     })
 
-    it('Claim team elw as a team member 2', async () => {
-        let result: any
+    it('Claim the vested tokens of a revoked team vesting', async () => {
+        const transaction = await createClaimTeamMemberElwTransaction(member.publicKey)
+        const txSig = await signAndSendTransaction(transaction, [], member)
+        console.log('Your transaction signature', txSig)
+
+        // a revoked vesting does not grow, so the claim takes all of it
+        const vesting = await getTeamVestingAccountData(member.publicKey)
+        const memberClaim = await getTeamMemberClaimAccountData(member.publicKey)
+        expect(memberClaim?.amount).to.be.equal(vesting?.totalAmount)
+    })
+
+    it('Create a team vesting after the revoked one is claimed', async () => {
+        const txSig = await executeMultisigTransaction(
+            'test team vesting recreate',
+            await createCreateTeamVestingTransaction(
+                member.publicKey,
+                totalAmount,
+                getStreamingSchedule()
+            )
+        )
+        console.log('Your transaction signature', txSig)
+
+        const vesting = await getTeamVestingAccountData(member.publicKey)
+        const memberClaim = await getTeamMemberClaimAccountData(member.publicKey)
+        expect(vesting?.totalAmount).to.be.equal(totalAmount)
+        expect(vesting?.isRevoked).to.be.equal(false)
+        expect(memberClaim?.amount).to.be.equal(0)
+    })
+})
+
+describe('Team Streaming Claim', () => {
+    // a prime duration, so the streamed amounts do not divide evenly
+    const streamAmount = 1000
+    const streamDuration = 97
+    let member: anchor.Wallet
+    let endTime: number
+
+    const getElwBalance = async () => {
+        const result = await getTokenAccountInfo(
+            getAssociatedTokenAddressSync(await getElwMint(), member.publicKey)
+        )
+        return result?.parsed.info.tokenAmount.uiAmount ?? 0
+    }
+
+    before(async () => {
+        member = await createFundedWallet()
+        const now = Math.floor(Date.now() / 1000)
+        endTime = now + streamDuration
+        await executeMultisigTransaction(
+            'test team vesting stream',
+            await createCreateTeamVestingTransaction(member.publicKey, streamAmount, {
+                startTime: now,
+                cliffTime: now,
+                endTime,
+                tgePercentage: 1000 // 10% at the cliff
+            })
+        )
+    })
+
+    it('Claim in the middle of the stream', async () => {
+        const txSig = await signAndSendTransaction(
+            await createClaimTeamMemberElwTransaction(member.publicKey),
+            [],
+            member
+        )
+        console.log('Your transaction signature', txSig)
+
+        // the TGE share plus what streamed so far, never the whole amount
+        const memberClaim = await getTeamMemberClaimAccountData(member.publicKey)
+        expect(memberClaim?.amount).to.be.greaterThanOrEqual(streamAmount * 0.1)
+        expect(memberClaim?.amount).to.be.lessThan(streamAmount)
+        expect(await getElwBalance()).to.be.equal(memberClaim?.amount)
+    })
+
+    it('Claim twice in the same second', async () => {
+        const transactions = await Promise.all([
+            createClaimTeamMemberElwTransaction(member.publicKey),
+            createClaimTeamMemberElwTransaction(member.publicKey)
+        ])
+        const claimBefore = await getTeamMemberClaimAccountData(member.publicKey)
         try {
-            const transaction = await createClaimTeamMemberElwTransaction(clientWallet.publicKey)
-            const txSig = await signAndSendTransaction(transaction, [], clientWallet)
-            console.log('Your transaction signature', txSig)
-            result = true
+            await signAndSendTransaction(transactions[0], [], member)
+            await signAndSendTransaction(transactions[1], [], member)
         } catch (error: any) {
-            if (error instanceof SendTransactionError) {
-                console.log(error.logs)
-            }
-            result =
-                error.message === ErrorCode.PeriodNotReached ||
-                error.message === ErrorCode.NotEnoughBalanceInVault ||
-                error.message === ErrorCode.AlreadyClaimedForThisPeriod
+            // nothing streamed since the last claim
+            expect(error.message).to.be.equal(ErrorCode.NoClaimableRewards)
+        }
+        const claimAfter = await getTeamMemberClaimAccountData(member.publicKey)
+        expect(claimAfter?.amount).to.be.lessThan(streamAmount)
+        expect(claimAfter?.amount).to.be.greaterThanOrEqual(claimBefore?.amount ?? 0)
+    })
+
+    it('Claim after the end of the stream', async () => {
+        const waitMs = (endTime + 2) * 1000 - Date.now()
+        if (waitMs > 0) {
+            await new Promise((resolve) => setTimeout(resolve, waitMs))
+        }
+
+        const teamVaultBefore = await getTeamVaultElwBalance()
+        const claimBefore = await getTeamMemberClaimAccountData(member.publicKey)
+        const txSig = await signAndSendTransaction(
+            await createClaimTeamMemberElwTransaction(member.publicKey),
+            [],
+            member
+        )
+        console.log('Your transaction signature', txSig)
+
+        // the last claim takes the rounding remainder, so the total is paid exactly
+        const memberClaim = await getTeamMemberClaimAccountData(member.publicKey)
+        expect(memberClaim?.amount).to.be.equal(streamAmount)
+        expect(await getElwBalance()).to.be.equal(streamAmount)
+
+        // the team vault is closed once the last claim empties it
+        const finalAmount = streamAmount - (claimBefore?.amount ?? 0)
+        const teamVaultAta = await getTokenAccountInfo(
+            await getVaultAccountElwAta(VaultAccount.Team)
+        )
+        if (Math.abs(teamVaultBefore.amount - finalAmount) < 1e-9) {
+            expect(teamVaultAta).to.be.equal(null)
+        } else {
+            expect(teamVaultAta?.parsed.info.tokenAmount.uiAmount).to.be.closeTo(
+                teamVaultBefore.amount - finalAmount,
+                1e-6
+            )
         }
-        expect(result).to.be.equal(true)
     })
 
-    it('Claim team elw as a team member 3', async () => {
-        let result: any
+    it('Claim after the final claim', async () => {
         try {
-            const transaction = await createClaimTeamMemberElwTransaction(clientWallet2.publicKey)
-            const txSig = await signAndSendTransaction(transaction, [], clientWallet2)
-            console.log('Your transaction signature', txSig)
-            result = true
+            await signAndSendTransaction(
+                await createClaimTeamMemberElwTransaction(member.publicKey),
+                [],
+                member
+            )
+            expect.fail('Nothing is left to claim')
         } catch (error: any) {
-            if (error instanceof SendTransactionError) {
-                console.log(error.logs)
-            }
-            result =
-                error.message === ErrorCode.PeriodNotReached ||
-                error.message === ErrorCode.NotEnoughBalanceInVault ||
-                error.message === ErrorCode.AlreadyClaimedForThisPeriod
+            expect(error.message).to.be.equal(ErrorCode.NoClaimableRewards)
         }
-        expect(result).to.be.equal(true)
     })
 })
@@ -0,0 +1,118 @@
+import { expect } from 'chai'
+import * as anchor from '@coral-xyz/anchor'
+import { createFundedWallet } from '../wallet'
+import { executeMultisigTransaction } from '../multisig'
+import { getAssociatedTokenAddressSync } from '@solana/spl-token'
+import {
+    ErrorCode,
+    VestingSchedule,
+    createClaimTeamMemberElwTransaction,
+    createCreateTeamVestingTransaction,
+    createRotateTeamMemberTransaction,
+    getElwMint,
+    getTeamMemberClaimAccountData,
+    getTeamVestingAccountData,
+    getTokenAccountInfo,
+    signAndSendTransaction
+} from '../../app'
+
+const totalAmount = 500
+
+// already ended, so the vesting is claimable in full
+const getVestedSchedule = (): VestingSchedule => {
+    const now = Math.floor(Date.now() / 1000)
+    return { startTime: now - 7200, cliffTime: now - 7200, endTime: now - 3600, tgePercentage: 0 }
+}
+
+const getElwBalance = async (wallet: anchor.Wallet) => {
+    const result = await getTokenAccountInfo(
+        getAssociatedTokenAddressSync(await getElwMint(), wallet.publicKey)
+    )
+    return result?.parsed.info.tokenAmount.uiAmount ?? 0
+}
+
+const createVesting = async (member: anchor.Wallet) => {
+    await executeMultisigTransaction(
+        'test team vesting create',
+        await createCreateTeamVestingTransaction(member.publicKey, totalAmount, getVestedSchedule())
+    )
+}
+
+const rotateMember = async (member: anchor.Wallet, newMember: anchor.Wallet) => {
+    const txSig = await executeMultisigTransaction(
+        'test team member rotate',
+        await createRotateTeamMemberTransaction(member.publicKey, newMember.publicKey)
+    )
+    console.log('Your transaction signature', txSig)
+}
+
+describe('Team Member Rotation', () => {
+    it('Rotate a member who already claimed', async () => {
+        const [member, newMember] = await Promise.all([createFundedWallet(), createFundedWallet()])
+        await createVesting(member)
+        await signAndSendTransaction(
+            await createClaimTeamMemberElwTransaction(member.publicKey),
+            [],
+            member
+        )
+        const memberClaim = await getTeamMemberClaimAccountData(member.publicKey)
+        expect(memberClaim?.amount).to.be.equal(totalAmount)
+
+        await rotateMember(member, newMember)
+
+        // vesting and progress live under the new wallet only
+        expect(await getTeamVestingAccountData(member.publicKey)).to.be.equal(null)
+        expect(await getTeamMemberClaimAccountData(member.publicKey)).to.be.equal(null)
+        const newVesting = await getTeamVestingAccountData(newMember.publicKey)
+        const newMemberClaim = await getTeamMemberClaimAccountData(newMember.publicKey)
+        expect(newVesting?.member.toBase58()).to.be.equal(newMember.publicKey.toBase58())
+        expect(newVesting?.totalAmount).to.be.equal(totalAmount)
+        expect(newMemberClaim?.amount).to.be.equal(memberClaim?.amount)
+        expect(newMemberClaim?.lastPeriod).to.be.equal(memberClaim?.lastPeriod)
+
+        // what the old wallet was paid can not be claimed again
+        try {
+            await signAndSendTransaction(
+                await createClaimTeamMemberElwTransaction(newMember.publicKey),
+                [],
+                newMember
+            )
+            expect.fail('Claimed tokens should not be claimable by the new wallet')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.NoClaimableRewards)
+        }
+        expect(await getElwBalance(newMember)).to.be.equal(0)
+    })
+
+    it('Rotate a member who never claimed', async () => {
+        const [member, newMember] = await Promise.all([createFundedWallet(), createFundedWallet()])
+        await createVesting(member)
+        expect(await getTeamMemberClaimAccountData(member.publicKey)).to.be.equal(null)
+
+        await rotateMember(member, newMember)
+
+        // the empty claim PDA starts the new wallet from zero
+        const newMemberClaim = await getTeamMemberClaimAccountData(newMember.publicKey)
+        expect(newMemberClaim?.amount).to.be.equal(0)
+
+        await signAndSendTransaction(
+            await createClaimTeamMemberElwTransaction(newMember.publicKey),
+            [],
+            newMember
+        )
+        expect(await getElwBalance(newMember)).to.be.equal(totalAmount)
+    })
+
+    it('Rotate to a wallet that has a vesting', async () => {
+        const [member, newMember] = await Promise.all([createFundedWallet(), createFundedWallet()])
+        // multisig proposals are indexed, so they go one by one
+        await createVesting(member)
+        await createVesting(newMember)
+        try {
+            await rotateMember(member, newMember)
+            expect.fail('A wallet holds one vesting only')
+        } catch (error: any) {
+            expect(error.message).to.be.equal(ErrorCode.PdaAlreadyInUse)
+        }
+    })
+})
@@ -0,0 +1,17 @@
+import * as anchor from '@coral-xyz/anchor'
+import ElowenProgram, { signAndSendTransaction } from '../app'
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram, Transaction } from '@solana/web3.js'
+
+// fresh wallet funded by the platform wallet, so every run starts from empty accounts
+export const createFundedWallet = async (sol: number = 0.05) => {
+    const wallet = new anchor.Wallet(Keypair.generate())
+    const transaction = new Transaction().add(
+        SystemProgram.transfer({
+            fromPubkey: ElowenProgram.wallet.publicKey,
+            toPubkey: wallet.publicKey,
+            lamports: Math.round(sol * LAMPORTS_PER_SOL)
+        })
+    )
+    await signAndSendTransaction(transaction)
+    return wallet
+}